@@ -0,0 +1,286 @@
+//! Known answers for a puzzle input, used to check every run against what the website already told us.
+//! Answers depend on the input, so each input file gets its own answers file right next to it:
+//!     input/day2_input.txt -> input/day2_input.answers
+//!
+//! The file is plain text with one fact per line, so it is easy to edit by hand:
+//!     part1 correct 526
+//!     part2 too_low 552
+//!     part2 wrong 560
+use std::{ fmt, fs, io, path::{ Path, PathBuf } };
+
+use colored::Colorize;
+
+use crate::solution::{ Answer, Part };
+
+/// Something we know about a part's answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fact {
+    Correct(Answer),
+    TooHigh(i64),
+    TooLow(i64),
+    /// Rejected without a hint, or a text answer that can't be compared.
+    Wrong(Answer),
+}
+
+impl Fact {
+    fn keyword(&self) -> &'static str {
+        match self {
+            Fact::Correct(_) => "correct",
+            Fact::TooHigh(_) => "too_high",
+            Fact::TooLow(_) => "too_low",
+            Fact::Wrong(_) => "wrong",
+        }
+    }
+
+    /// Reads a fact back from its keyword and value, as written in the answers file.
+    pub fn parse(keyword: &str, value: &str) -> Option<Fact> {
+        let Ok(answer) = value.parse::<Answer>();
+        match (keyword, answer) {
+            ("correct", answer) => Some(Fact::Correct(answer)),
+            ("too_high", Answer::Num(num)) => Some(Fact::TooHigh(num)),
+            ("too_low", Answer::Num(num)) => Some(Fact::TooLow(num)),
+            ("wrong", answer) => Some(Fact::Wrong(answer)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Fact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fact::Correct(answer) | Fact::Wrong(answer) => write!(f, "{} {}", self.keyword(), answer),
+            Fact::TooHigh(num) | Fact::TooLow(num) => write!(f, "{} {}", self.keyword(), num),
+        }
+    }
+}
+
+/// The outcome of checking an answer against what we know.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "{}", "PASS".green()),
+            Verdict::Fail(reason) => write!(f, "{} ({})", "FAIL".red(), reason),
+            Verdict::Unknown => write!(f, "{}", "UNKNOWN".yellow()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct PartAnswers {
+    correct: Option<Answer>,
+    /// Lowest answer known to be too high.
+    too_high: Option<i64>,
+    /// Highest answer known to be too low.
+    too_low: Option<i64>,
+    wrong: Vec<Answer>,
+}
+
+impl PartAnswers {
+    fn record(&mut self, fact: Fact) {
+        match fact {
+            Fact::Correct(answer) => self.correct = Some(answer),
+            Fact::TooHigh(num) => self.too_high = Some(self.too_high.map_or(num, |known| known.min(num))),
+            Fact::TooLow(num) => self.too_low = Some(self.too_low.map_or(num, |known| known.max(num))),
+            Fact::Wrong(answer) => {
+                if !self.wrong.contains(&answer) {
+                    self.wrong.push(answer);
+                }
+            }
+        }
+    }
+
+    fn check(&self, answer: &Answer) -> Verdict {
+        if let Some(correct) = &self.correct {
+            return match correct == answer {
+                true => Verdict::Pass,
+                false => Verdict::Fail(format!("expected {}", correct)),
+            };
+        }
+        if self.wrong.contains(answer) {
+            return Verdict::Fail(format!("{} was already rejected", answer));
+        }
+        if let Answer::Num(num) = answer {
+            if let Some(too_high) = self.too_high.filter(|too_high| num >= too_high) {
+                return Verdict::Fail(format!("{} was too high", too_high));
+            }
+            if let Some(too_low) = self.too_low.filter(|too_low| num <= too_low) {
+                return Verdict::Fail(format!("{} was too low", too_low));
+            }
+        }
+        Verdict::Unknown
+    }
+
+    fn facts(&self) -> Vec<Fact> {
+        self.correct.iter().cloned().map(Fact::Correct)
+            .chain(self.too_high.map(Fact::TooHigh))
+            .chain(self.too_low.map(Fact::TooLow))
+            .chain(self.wrong.iter().cloned().map(Fact::Wrong))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AnswerStore {
+    path: PathBuf,
+    part1: PartAnswers,
+    part2: PartAnswers,
+}
+
+impl AnswerStore {
+    /// Where the answers for a given input file live.
+    pub fn path_for_input(input_path: &Path) -> PathBuf {
+        input_path.with_extension("answers")
+    }
+
+    /// Loads the answers that sit next to `input_path`.
+    /// A missing answers file just means we don't know anything yet.
+    pub fn load_for_input(input_path: &Path) -> io::Result<AnswerStore> {
+        AnswerStore::load(&AnswerStore::path_for_input(input_path))
+    }
+
+    pub fn load(path: &Path) -> io::Result<AnswerStore> {
+        let mut store = AnswerStore {
+            path: path.to_path_buf(),
+            part1: PartAnswers::default(),
+            part2: PartAnswers::default(),
+        };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(store),
+            Err(err) => return Err(err),
+        };
+
+        for (line_number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.splitn(3, ' ');
+            let part = match words.next() {
+                Some("part1") => Part::One,
+                Some("part2") => Part::Two,
+                _ => return Err(bad_line(path, line_number, line)),
+            };
+            let fact = match (words.next(), words.next()) {
+                (Some(keyword), Some(value)) => Fact::parse(keyword, value),
+                _ => None,
+            }.ok_or_else(|| bad_line(path, line_number, line))?;
+            store.record(part, fact);
+        }
+        Ok(store)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let lines: Vec<String> = Part::BOTH
+            .iter()
+            .flat_map(|part| {
+                self.part(*part).facts().into_iter().map(move |fact| format!("part{} {}", part, fact))
+            })
+            .collect();
+        fs::write(&self.path, lines.join("\n") + "\n")
+    }
+
+    fn part(&self, part: Part) -> &PartAnswers {
+        match part {
+            Part::One => &self.part1,
+            Part::Two => &self.part2,
+        }
+    }
+
+    pub fn record(&mut self, part: Part, fact: Fact) {
+        match part {
+            Part::One => self.part1.record(fact),
+            Part::Two => self.part2.record(fact),
+        }
+    }
+
+    pub fn check(&self, part: Part, answer: &Answer) -> Verdict {
+        self.part(part).check(answer)
+    }
+}
+
+fn bad_line(path: &Path, line_number: usize, line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}:{}: can't read answer line '{}'", path.display(), line_number + 1, line)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_dir;
+
+    fn facts(facts: &[Fact]) -> PartAnswers {
+        let mut answers = PartAnswers::default();
+        facts.iter().cloned().for_each(|fact| answers.record(fact));
+        answers
+    }
+
+    #[test]
+    fn correct_answer_decides_everything() {
+        let answers = facts(&[Fact::TooLow(10), Fact::Correct(Answer::Num(15))]);
+        assert_eq!(answers.check(&Answer::Num(15)), Verdict::Pass);
+        assert_eq!(answers.check(&Answer::Num(16)), Verdict::Fail(String::from("expected 15")));
+    }
+
+    #[test]
+    fn bounds_keep_the_tightest_hint() {
+        let answers = facts(&[Fact::TooHigh(100), Fact::TooHigh(80), Fact::TooLow(10), Fact::TooLow(20)]);
+        assert_eq!(answers.check(&Answer::Num(80)), Verdict::Fail(String::from("80 was too high")));
+        assert_eq!(answers.check(&Answer::Num(90)), Verdict::Fail(String::from("80 was too high")));
+        assert_eq!(answers.check(&Answer::Num(20)), Verdict::Fail(String::from("20 was too low")));
+        assert_eq!(answers.check(&Answer::Num(15)), Verdict::Fail(String::from("20 was too low")));
+        assert_eq!(answers.check(&Answer::Num(50)), Verdict::Unknown);
+        assert_eq!(answers.check(&Answer::from("abc")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let answers = facts(&[Fact::Wrong(Answer::Num(560)), Fact::Wrong(Answer::from("abc")), Fact::Wrong(Answer::Num(560))]);
+        assert_eq!(answers.wrong.len(), 2);
+        assert_eq!(answers.check(&Answer::Num(560)), Verdict::Fail(String::from("560 was already rejected")));
+        assert_eq!(answers.check(&Answer::from("abc")), Verdict::Fail(String::from("abc was already rejected")));
+        assert_eq!(answers.check(&Answer::Num(561)), Verdict::Unknown);
+    }
+
+    #[test]
+    fn store_survives_a_save_and_load() {
+        let input = temp_dir("answers_store").join("day2_input.txt");
+        let path = AnswerStore::path_for_input(&input);
+
+        let mut store = AnswerStore::load_for_input(&input).unwrap();
+        assert_eq!(store.check(Part::One, &Answer::Num(526)), Verdict::Unknown);
+        store.record(Part::One, Fact::Correct(Answer::Num(526)));
+        store.record(Part::Two, Fact::TooLow(552));
+        store.record(Part::Two, Fact::TooHigh(600));
+        store.record(Part::Two, Fact::Wrong(Answer::Num(560)));
+        store.save().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "part1 correct 526\npart2 too_high 600\npart2 too_low 552\npart2 wrong 560\n"
+        );
+        let loaded = AnswerStore::load_for_input(&input).unwrap();
+        for part in Part::BOTH {
+            assert_eq!(loaded.part(part).facts(), store.part(part).facts());
+        }
+        assert_eq!(loaded.check(Part::Two, &Answer::Num(560)), Verdict::Fail(String::from("560 was already rejected")));
+    }
+
+    #[test]
+    fn rejects_unreadable_lines() {
+        let path = temp_dir("answers_bad").join("day2_input.answers");
+        fs::write(&path, "# comment\npart1 correct 1\npart3 correct 2\n").unwrap();
+        let err = AnswerStore::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().ends_with(":3: can't read answer line 'part3 correct 2'"));
+    }
+}
@@ -0,0 +1,171 @@
+//! Timing for parse, part 1 and part 2 of each day.
+//! Every run builds a fresh solution and parses again, so parse times aren't flattered by a warm struct.
+//! Reading the input from disk happens once up front and isn't part of any measurement.
+use std::time::{ Duration, Instant };
+
+use colored::Colorize;
+
+use aoc_2024::registry;
+use aoc_2024::solution::Part;
+
+use crate::input::{ self, InputSource };
+
+/// Runs `f` and returns its result with how long it took.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Durations at whatever precision makes them readable.
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1_000_000.0;
+    if micros < 1_000.0 {
+        format!("{:.1}µs", micros)
+    } else if micros < 1_000_000.0 {
+        format!("{:.2}ms", micros / 1_000.0)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics on an empty sample set; every benchmark takes at least one run.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DayTimings {
+    pub day: u8,
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayTimings {
+    /// What a single run of the whole day typically costs.
+    pub fn total(&self) -> Duration {
+        self.parse.median + self.part1.median + self.part2.median
+    }
+}
+
+pub fn bench_day(day: u8, runs: usize, source: &InputSource) -> Result<DayTimings, String> {
+    let build = || registry::lookup(registry::YEAR, day).ok_or_else(|| format!("no solution for day {}", day));
+    let input = input::load(day, source, build()?.as_ref())?;
+
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut part_samples = [Vec::with_capacity(runs), Vec::with_capacity(runs)];
+    for _ in 0..runs.max(1) {
+        let mut solution = build()?;
+        let (parsed, parse_time) = time(|| solution.parse(&input.text));
+        parsed.map_err(|err| input.locate(err).to_string())?;
+        parse_samples.push(parse_time);
+        for (samples, part) in part_samples.iter_mut().zip(Part::BOTH) {
+            let (solved, part_time) = time(|| solution.solve(part));
+            solved.map_err(|err| input.locate(err).to_string())?;
+            samples.push(part_time);
+        }
+    }
+
+    let [part1_samples, part2_samples] = part_samples;
+    Ok(DayTimings {
+        day,
+        runs: parse_samples.len(),
+        parse: Stats::from_samples(parse_samples),
+        part1: Stats::from_samples(part1_samples),
+        part2: Stats::from_samples(part2_samples),
+    })
+}
+
+/// Prints one row per day and returns how many days went over `budget`.
+pub fn print_table(timings: &[DayTimings], budget: Option<Duration>) -> usize {
+    let stat_cell = |stats: Stats| {
+        format!(
+            "{} ({}-{})",
+            format_duration(stats.median),
+            format_duration(stats.min),
+            format_duration(stats.max)
+        )
+    };
+
+    println!("{:>3}  {:>5}  {:<28}  {:<28}  {:<28}  {:>10}", "Day", "Runs", "Parse", "Part 1", "Part 2", "Total");
+    let mut over_budget = 0;
+    for day in timings {
+        let total = format!("{:>10}", format_duration(day.total()));
+        let total = match budget {
+            Some(budget) if day.total() > budget => {
+                over_budget += 1;
+                total.red().to_string()
+            }
+            _ => total,
+        };
+        println!(
+            "{:>3}  {:>5}  {:<28}  {:<28}  {:<28}  {}",
+            day.day,
+            day.runs,
+            stat_cell(day.parse),
+            stat_cell(day.part1),
+            stat_cell(day.part2),
+            total
+        );
+    }
+    println!("Times are median (min-max) over all runs.");
+
+    let all_days: Duration = timings.iter().map(|day| day.total()).sum();
+    println!("All days: {}", format_duration(all_days));
+    if let Some(budget) = budget {
+        println!("{} day(s) over the {} budget", over_budget, format_duration(budget));
+    }
+    over_budget
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_pick_a_readable_unit() {
+        assert_eq!(format_duration(Duration::from_nanos(1_500)), "1.5µs");
+        assert_eq!(format_duration(Duration::from_micros(999)), "999.0µs");
+        assert_eq!(format_duration(Duration::from_micros(2_345)), "2.35ms");
+        assert_eq!(format_duration(Duration::from_millis(1_500)), "1.50s");
+    }
+
+    #[test]
+    fn stats_take_min_median_and_max() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn bench_day_runs_every_stage_each_time() {
+        let timings = bench_day(1, 3, &InputSource::Example(1)).unwrap();
+        assert_eq!((timings.day, timings.runs), (1, 3));
+        for stats in [timings.parse, timings.part1, timings.part2] {
+            assert!(stats.min <= stats.median && stats.median <= stats.max);
+        }
+        assert_eq!(timings.total(), timings.parse.median + timings.part1.median + timings.part2.median);
+
+        // Zero runs still measures once
+        assert_eq!(bench_day(1, 0, &InputSource::Example(1)).unwrap().runs, 1);
+        assert_eq!(bench_day(99, 3, &InputSource::Default).err(), Some(String::from("no solution for day 99")));
+    }
+}
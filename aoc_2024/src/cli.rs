@@ -0,0 +1,389 @@
+//! Command line parsing for the aoc_2024 binary.
+//! Kept dependency-free on purpose; the argument surface is tiny and hand parsing keeps compile times down.
+use std::{ fmt, path::PathBuf, time::Duration };
+
+use aoc_2024::answers::Fact;
+use aoc_2024::solution::Part;
+
+use crate::input::InputSource;
+
+pub const USAGE: &str = "\
+Usage:
+    aoc_2024 run --day <N> [--part <1|2>] [--input <PATH> | --stdin | --example <K>]
+    aoc_2024 run --all [--example <K>]
+    aoc_2024 bench (--day <N> | --all) [--runs <R>] [--budget <MS>] [--example <K>]
+    aoc_2024 compare --baseline <COMMIT> [--against <COMMIT>] [--threshold <PERCENT>] [--example <K>]
+    aoc_2024 new --day <N>
+    aoc_2024 fetch --day <N>
+    aoc_2024 submit --day <N> --part <1|2> [--wait]
+    aoc_2024 record --day <N> --part <1|2> (--correct | --too-high | --too-low | --wrong) <ANSWER>
+    aoc_2024 help";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    /// A single day, optionally narrowed to a single part. `None` runs both parts.
+    Day(u8, Option<Part>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(Selection, InputSource),
+    /// Times parse and both parts over `runs` runs, flagging days slower than `budget`.
+    Bench { selection: Selection, source: InputSource, runs: usize, budget: Option<Duration> },
+    /// Checks benchmark history for stages that got slower than `baseline` by more than `threshold` percent.
+    /// Without `against`, the most recently benchmarked commit is compared.
+    Compare { baseline: String, against: Option<String>, source: InputSource, threshold: u32 },
+    /// Generates the module, registry entry and input files for a new day.
+    New { day: u8 },
+    /// Downloads a day's input unless it's already cached.
+    Fetch { day: u8 },
+    /// Solves a part and posts the answer to the site. `wait` sits out any throttle instead of refusing.
+    Submit { day: u8, part: Part, wait: bool },
+    /// Remembers something the website told us about an answer.
+    Record { day: u8, part: Part, fact: Fact },
+    Help,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CliError(pub String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Pulls the value following a `--flag`, erroring if the flag was the last argument.
+fn flag_value<'a>(flag: &str, args: &mut impl Iterator<Item = &'a String>) -> Result<&'a String, CliError> {
+    args.next().ok_or_else(|| CliError(format!("{} requires a value", flag)))
+}
+
+fn parse_part(raw: &str) -> Result<Part, CliError> {
+    Part::from_number(raw).ok_or_else(|| CliError(format!("part must be 1 or 2, got '{}'", raw)))
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, raw: &str) -> Result<T, CliError> {
+    raw.parse::<T>().map_err(|_| CliError(format!("{} must be a number, got '{}'", name, raw)))
+}
+
+fn parse_day(raw: &str) -> Result<u8, CliError> {
+    raw.parse::<u8>().map_err(|_| CliError(format!("day must be a number, got '{}'", raw)))
+}
+
+fn parse_run(args: &[String]) -> Result<Command, CliError> {
+    let mut day: Option<u8> = None;
+    let mut part: Option<Part> = None;
+    let mut all = false;
+    let mut source = InputSource::Default;
+
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        let next_source = match arg.as_str() {
+            "--day" | "-d" => {
+                day = Some(parse_day(flag_value(arg, &mut remaining)?)?);
+                None
+            }
+            "--part" | "-p" => {
+                part = Some(parse_part(flag_value(arg, &mut remaining)?)?);
+                None
+            }
+            "--all" | "-a" => {
+                all = true;
+                None
+            }
+            "--input" | "-i" => {
+                let path = flag_value(arg, &mut remaining)?;
+                match path.as_str() {
+                    "-" => Some(InputSource::Stdin),
+                    path => Some(InputSource::Path(PathBuf::from(path))),
+                }
+            }
+            "--stdin" => Some(InputSource::Stdin),
+            "--example" | "-e" => {
+                let raw = flag_value(arg, &mut remaining)?;
+                Some(InputSource::Example(parse_number("example", raw)?))
+            }
+            other => {
+                return Err(CliError(format!("unexpected argument '{}'", other)));
+            }
+        };
+        if let Some(next_source) = next_source {
+            if source != InputSource::Default {
+                return Err(CliError(String::from("only one of --input, --stdin or --example can be given")));
+            }
+            source = next_source;
+        }
+    }
+
+    match (all, day, part, &source) {
+        (true, None, None, InputSource::Default | InputSource::Example(_)) => Ok(Command::Run(Selection::All, source)),
+        (true, None, None, _) => Err(CliError(String::from("--all only supports the default input or --example"))),
+        (true, _, _, _) => Err(CliError(String::from("--all cannot be combined with --day or --part"))),
+        (false, Some(day), part, _) => Ok(Command::Run(Selection::Day(day, part), source)),
+        (false, None, _, _) => Err(CliError(String::from("run requires --day <N> or --all"))),
+    }
+}
+
+fn parse_bench(args: &[String]) -> Result<Command, CliError> {
+    let mut day: Option<u8> = None;
+    let mut all = false;
+    let mut source = InputSource::Default;
+    let mut runs: usize = 10;
+    let mut budget: Option<Duration> = None;
+
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        match arg.as_str() {
+            "--day" | "-d" => {
+                day = Some(parse_day(flag_value(arg, &mut remaining)?)?);
+            }
+            "--all" | "-a" => {
+                all = true;
+            }
+            "--example" | "-e" => {
+                let raw = flag_value(arg, &mut remaining)?;
+                source = InputSource::Example(parse_number("example", raw)?);
+            }
+            "--runs" | "-r" => {
+                let raw = flag_value(arg, &mut remaining)?;
+                runs = parse_number::<usize>("runs", raw)?.max(1);
+            }
+            "--budget" | "-b" => {
+                let raw = flag_value(arg, &mut remaining)?;
+                let millis = parse_number::<f64>("budget", raw)?;
+                budget = Some(
+                    Duration::try_from_secs_f64(millis / 1000.0).map_err(|_| {
+                        CliError(format!("budget must be a positive number of milliseconds, got '{}'", raw))
+                    })?
+                );
+            }
+            other => {
+                return Err(CliError(format!("unexpected argument '{}'", other)));
+            }
+        }
+    }
+
+    let selection = match (all, day) {
+        (true, None) => Selection::All,
+        (false, Some(day)) => Selection::Day(day, None),
+        _ => return Err(CliError(String::from("bench requires exactly one of --day <N> or --all"))),
+    };
+    Ok(Command::Bench { selection, source, runs, budget })
+}
+
+fn parse_compare(args: &[String]) -> Result<Command, CliError> {
+    let mut baseline: Option<String> = None;
+    let mut against: Option<String> = None;
+    let mut source = InputSource::Default;
+    let mut threshold: u32 = 10;
+
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        match arg.as_str() {
+            "--baseline" => {
+                baseline = Some(flag_value(arg, &mut remaining)?.clone());
+            }
+            "--against" => {
+                against = Some(flag_value(arg, &mut remaining)?.clone());
+            }
+            "--threshold" | "-t" => {
+                let raw = flag_value(arg, &mut remaining)?;
+                threshold = parse_number("threshold", raw.trim_end_matches('%'))?;
+            }
+            "--example" | "-e" => {
+                let raw = flag_value(arg, &mut remaining)?;
+                source = InputSource::Example(parse_number("example", raw)?);
+            }
+            other => {
+                return Err(CliError(format!("unexpected argument '{}'", other)));
+            }
+        }
+    }
+
+    baseline
+        .map(|baseline| Command::Compare { baseline, against, source, threshold })
+        .ok_or_else(|| CliError(String::from("compare requires --baseline <COMMIT>")))
+}
+
+/// Parses the `--day <N>` that `new` and `fetch` take and nothing else.
+fn parse_day_only(command: &str, args: &[String]) -> Result<u8, CliError> {
+    let mut day: Option<u8> = None;
+
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        match arg.as_str() {
+            "--day" | "-d" => {
+                day = Some(parse_day(flag_value(arg, &mut remaining)?)?);
+            }
+            other => {
+                return Err(CliError(format!("unexpected argument '{}'", other)));
+            }
+        }
+    }
+
+    day.ok_or_else(|| CliError(format!("{} requires --day <N>", command)))
+}
+
+fn parse_submit(args: &[String]) -> Result<Command, CliError> {
+    let mut day: Option<u8> = None;
+    let mut part: Option<Part> = None;
+    let mut wait = false;
+
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        match arg.as_str() {
+            "--day" | "-d" => {
+                day = Some(parse_day(flag_value(arg, &mut remaining)?)?);
+            }
+            "--part" | "-p" => {
+                part = Some(parse_part(flag_value(arg, &mut remaining)?)?);
+            }
+            "--wait" | "-w" => {
+                wait = true;
+            }
+            other => {
+                return Err(CliError(format!("unexpected argument '{}'", other)));
+            }
+        }
+    }
+
+    match (day, part) {
+        (Some(day), Some(part)) => Ok(Command::Submit { day, part, wait }),
+        _ => Err(CliError(String::from("submit requires --day and --part"))),
+    }
+}
+
+fn parse_record(args: &[String]) -> Result<Command, CliError> {
+    let mut day: Option<u8> = None;
+    let mut part: Option<Part> = None;
+    let mut fact: Option<Fact> = None;
+
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        match arg.as_str() {
+            "--day" | "-d" => {
+                day = Some(parse_day(flag_value(arg, &mut remaining)?)?);
+            }
+            "--part" | "-p" => {
+                part = Some(parse_part(flag_value(arg, &mut remaining)?)?);
+            }
+            "--correct" | "--too-high" | "--too-low" | "--wrong" => {
+                let keyword = arg.trim_start_matches("--").replace('-', "_");
+                let value = flag_value(arg, &mut remaining)?;
+                fact = Some(
+                    Fact::parse(&keyword, value).ok_or_else(||
+                        CliError(format!("{} needs a number, got '{}'", arg, value))
+                    )?
+                );
+            }
+            other => {
+                return Err(CliError(format!("unexpected argument '{}'", other)));
+            }
+        }
+    }
+
+    match (day, part, fact) {
+        (Some(day), Some(part), Some(fact)) => Ok(Command::Record { day, part, fact }),
+        _ => Err(CliError(String::from("record requires --day, --part and one answer flag"))),
+    }
+}
+
+/// Parses the arguments following the program name.
+pub fn parse_args(args: &[String]) -> Result<Command, CliError> {
+    match args.split_first() {
+        Some((command, rest)) =>
+            match command.as_str() {
+                "run" => parse_run(rest),
+                "bench" => parse_bench(rest),
+                "compare" => parse_compare(rest),
+                "new" => parse_day_only("new", rest).map(|day| Command::New { day }),
+                "fetch" => parse_day_only("fetch", rest).map(|day| Command::Fetch { day }),
+                "submit" => parse_submit(rest),
+                "record" => parse_record(rest),
+                "help" | "--help" | "-h" => Ok(Command::Help),
+                other => Err(CliError(format!("unknown command '{}'", other))),
+            }
+        None => Ok(Command::Help),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn run_selects_days_and_parts() {
+        let run = |selection| Ok(Command::Run(selection, InputSource::Default));
+        assert_eq!(parse_run(&args("--day 8")), run(Selection::Day(8, None)));
+        assert_eq!(parse_run(&args("-d 4 -p 2")), run(Selection::Day(4, Some(Part::Two))));
+        assert_eq!(parse_run(&args("--all")), run(Selection::All));
+    }
+
+    #[test]
+    fn run_picks_an_input_source() {
+        let day = |source| Ok(Command::Run(Selection::Day(3, None), source));
+        assert_eq!(parse_run(&args("-d 3 --input in.txt")), day(InputSource::Path(PathBuf::from("in.txt"))));
+        assert_eq!(parse_run(&args("-d 3 --input -")), day(InputSource::Stdin));
+        assert_eq!(parse_run(&args("-d 3 --stdin")), day(InputSource::Stdin));
+        assert_eq!(parse_run(&args("-d 3 --example 2")), day(InputSource::Example(2)));
+        assert_eq!(parse_run(&args("--all -e 1")), Ok(Command::Run(Selection::All, InputSource::Example(1))));
+    }
+
+    #[test]
+    fn run_rejects_bad_selections() {
+        let error = |line: &str| parse_run(&args(line)).unwrap_err().0;
+        assert_eq!(error(""), "run requires --day <N> or --all");
+        assert_eq!(error("--all --day 3"), "--all cannot be combined with --day or --part");
+        assert_eq!(error("--day"), "--day requires a value");
+        assert_eq!(error("--day x"), "day must be a number, got 'x'");
+        assert_eq!(error("--day 1 --part 3"), "part must be 1 or 2, got '3'");
+        assert_eq!(error("--day 1 --fast"), "unexpected argument '--fast'");
+        assert_eq!(error("--day 1 --example two"), "example must be a number, got 'two'");
+        assert_eq!(error("--day 1 --stdin --example 1"), "only one of --input, --stdin or --example can be given");
+        assert_eq!(error("--all --stdin"), "--all only supports the default input or --example");
+    }
+
+    #[test]
+    fn fetch_needs_a_day() {
+        assert_eq!(parse_args(&args("fetch --day 5")), Ok(Command::Fetch { day: 5 }));
+        assert_eq!(parse_args(&args("fetch")), Err(CliError(String::from("fetch requires --day <N>"))));
+    }
+
+    #[test]
+    fn submit_needs_a_day_and_part() {
+        assert_eq!(
+            parse_args(&args("submit -d 6 -p 2 --wait")),
+            Ok(Command::Submit { day: 6, part: Part::Two, wait: true })
+        );
+        assert_eq!(parse_args(&args("submit -d 6")), Err(CliError(String::from("submit requires --day and --part"))));
+    }
+
+    #[test]
+    fn budget_must_be_a_usable_duration() {
+        for bad in ["-5", "nan", "inf", "1e300"] {
+            let parsed = parse_args(&args(&format!("bench --day 1 --budget {}", bad)));
+            assert!(matches!(parsed, Err(CliError(message)) if message.starts_with("budget must be")), "{}", bad);
+        }
+        assert_eq!(
+            parse_args(&args("bench --day 1 --budget 250")),
+            Ok(Command::Bench {
+                selection: Selection::Day(1, None),
+                source: InputSource::Default,
+                runs: 10,
+                budget: Some(Duration::from_millis(250)),
+            })
+        );
+    }
+
+    #[test]
+    fn no_command_is_help() {
+        assert_eq!(parse_args(&[]), Ok(Command::Help));
+        assert_eq!(parse_args(&args("--help")), Ok(Command::Help));
+        assert_eq!(parse_args(&args("walk")), Err(CliError(String::from("unknown command 'walk'"))));
+    }
+}
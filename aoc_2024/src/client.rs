@@ -0,0 +1,404 @@
+//! Talking to the Advent of Code website.
+//! The base URL can be pointed anywhere (AOC_BASE_URL), which is how the whole flow gets
+//! exercised against a local stand-in server instead of the real site.
+use std::{ env, fs, path::{ Path, PathBuf }, thread, time::{ Duration, SystemTime, UNIX_EPOCH } };
+
+use regex::Regex;
+
+use aoc_2024::answers::Fact;
+use aoc_2024::solution::{ Answer, Part };
+
+use crate::input;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The site asks automated tools to identify themselves, so every request carries this.
+pub const USER_AGENT: &str = concat!(
+    "aoc_2024/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/Checkroth/advent-of-code; personal puzzle input fetcher)"
+);
+
+/// Where the session token is read from when AOC_SESSION isn't set. Kept out of git.
+pub fn session_file() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".session")
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: String::from(base_url.trim_end_matches('/')),
+            session: String::from(session.trim()),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Builds a client from AOC_BASE_URL and AOC_SESSION, falling back to the real site and the `.session` file.
+    pub fn from_env() -> Result<Client, String> {
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| String::from(DEFAULT_BASE_URL));
+        let session = match env::var(SESSION_VAR) {
+            Ok(session) => session,
+            Err(_) => fs::read_to_string(session_file()).map_err(|_| {
+                format!("no session token: set {} or put it in {}", SESSION_VAR, session_file().display())
+            })?,
+        };
+        Ok(Client::new(&base_url, &session))
+    }
+
+    fn get(&self, path: &str) -> Result<String, String> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self.agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| describe_error(&url, err))?;
+        response.into_string().map_err(|err| format!("reading {} failed: {}", url, err))
+    }
+
+    fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self.agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form)
+            .map_err(|err| describe_error(&url, err))?;
+        response.into_string().map_err(|err| format!("reading {} failed: {}", url, err))
+    }
+
+    pub fn fetch_input(&self, year: u16, day: u8) -> Result<String, String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    pub fn submit_answer(&self, year: u16, day: u8, part: Part, answer: &Answer) -> Result<Submission, String> {
+        let level = part.to_string();
+        let answer = answer.to_string();
+        let body = self.post_form(
+            &format!("/{}/day/{}/answer", year, day),
+            &[("level", &level), ("answer", &answer)]
+        )?;
+        Ok(Submission::parse(&body))
+    }
+}
+
+/// What the site said about a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which way.
+    Wrong,
+    /// Submitted too soon after the last attempt; nothing was checked.
+    TooRecent,
+    /// The part is already solved, or part 1 isn't solved yet.
+    WrongLevel,
+    /// Something we don't know how to read; kept so it can be shown to the user.
+    Unrecognized(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub outcome: Outcome,
+    /// How long the site wants us to wait before the next attempt, if it said.
+    pub wait: Option<Duration>,
+}
+
+impl Submission {
+    /// Reads the answer page. Only the `<article>` text matters; the rest is site chrome.
+    pub fn parse(body: &str) -> Submission {
+        let article = Regex::new(r"(?s)<article>(.*?)</article>").unwrap()
+            .captures(body)
+            .map_or(body, |captures| captures.get(1).unwrap().as_str());
+        let text = Regex::new(r"<[^>]*>").unwrap().replace_all(article, "");
+
+        let outcome = if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("You gave an answer too recently") {
+            Outcome::TooRecent
+        } else if text.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if text.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else {
+            Outcome::Unrecognized(String::from(text.trim()))
+        };
+
+        Submission { outcome, wait: parse_wait(&text) }
+    }
+
+    /// The fact worth remembering about `answer`, if the site actually judged it.
+    pub fn fact(&self, answer: &Answer) -> Option<Fact> {
+        match (&self.outcome, answer) {
+            (Outcome::Correct, answer) => Some(Fact::Correct(answer.clone())),
+            (Outcome::TooHigh, Answer::Num(num)) => Some(Fact::TooHigh(*num)),
+            (Outcome::TooLow, Answer::Num(num)) => Some(Fact::TooLow(*num)),
+            (Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong, answer) => Some(Fact::Wrong(answer.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// Pulls a wait time out of messages like "You have 4m 12s left to wait" or "Please wait one minute".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let left_to_wait = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    if let Some(captures) = left_to_wait.captures(text) {
+        let minutes: u64 = captures.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+        let seconds: u64 = captures[2].parse().unwrap();
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+
+    let please_wait = Regex::new(r"[Pp]lease wait (\w+) minutes?").unwrap();
+    please_wait.captures(text).and_then(|captures| {
+        let minutes = match &captures[1] {
+            "one" => Some(1),
+            "two" => Some(2),
+            "five" => Some(5),
+            "ten" => Some(10),
+            digits => digits.parse::<u64>().ok(),
+        };
+        minutes.map(|minutes| Duration::from_secs(minutes * 60))
+    })
+}
+
+/// When we're next allowed to submit, as seconds since the epoch.
+/// Shared by every day, since the site throttles per account rather than per puzzle.
+pub fn throttle_file() -> PathBuf {
+    input::input_dir().join(".submit_wait")
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs())
+}
+
+/// Time left before another answer may be submitted.
+pub fn throttle_remaining() -> Option<Duration> {
+    let until: u64 = fs::read_to_string(throttle_file()).ok()?.trim().parse().ok()?;
+    until.checked_sub(now_secs()).filter(|left| *left > 0).map(Duration::from_secs)
+}
+
+pub fn set_throttle(wait: Duration) -> Result<(), String> {
+    let until = now_secs() + wait.as_secs();
+    fs::write(throttle_file(), until.to_string())
+        .map_err(|err| format!("can't write {}: {}", throttle_file().display(), err))
+}
+
+/// Blocks until the throttle has passed, printing how long that will be.
+pub fn wait_for_throttle() {
+    if let Some(left) = throttle_remaining() {
+        println!("waiting {}s before submitting, as the site asked", left.as_secs());
+        thread::sleep(left);
+    }
+}
+
+fn describe_error(url: &str, err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(400, _) => format!("{} rejected the session token (400), it may have expired", url),
+        ureq::Error::Status(404, _) => format!("{} was not found (404), the puzzle may not be unlocked yet", url),
+        ureq::Error::Status(code, _) => format!("{} answered with status {}", url, code),
+        ureq::Error::Transport(transport) => format!("couldn't reach {}: {}", url, transport),
+    }
+}
+
+/// Downloads a day's input to `path`, unless it is already there. Returns whether it downloaded.
+/// Cached inputs are never downloaded again; the site asks that inputs are only fetched once.
+/// An empty file, as left by `new`, doesn't count as cached.
+pub fn fetch_cached(path: &Path, client_for: impl FnOnce() -> Result<Client, String>, year: u16, day: u8) -> Result<bool, String> {
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(false);
+    }
+
+    let text = client_for()?.fetch_input(year, day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| format!("can't create {}: {}", dir.display(), err))?;
+    }
+    fs::write(path, text).map_err(|err| format!("can't write {}: {}", path.display(), err))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ io::{ Read, Write }, net::TcpListener, thread };
+
+    use super::*;
+    use crate::testing::temp_dir;
+
+    /// Answer pages as the site serves them, trimmed to the parts that matter.
+    const RIGHT_ANSWER: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<head><title>Day 1 - Advent of Code 2024</title></head>
+<body>
+<header><h1 class=\"title-global\"><a href=\"/\">Advent of Code</a></h1></header>
+<main>
+<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to finding the Chief Historian. <a href=\"/2024/day/1#part2\">[Continue to Part Two]</a></p></article>
+</main>
+</body>
+</html>";
+
+    const TOO_HIGH: &str = "<main>
+<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2024/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2024/day/6\">[Return to Day 6]</a></p></article>
+</main>";
+
+    const TOO_LOW: &str = "<main>
+<article><p>That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2024/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait 5 minutes before trying again. <a href=\"/2024/day/6\">[Return to Day 6]</a></p></article>
+</main>";
+
+    const WRONG: &str = "<main>
+<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href=\"/2024/day/3\">[Return to Day 3]</a></p></article>
+</main>";
+
+    const TOO_RECENT: &str = "<main>
+<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 12s left to wait. <a href=\"/2024/day/6\">[Return to Day 6]</a></p></article>
+</main>";
+
+    const WRONG_LEVEL: &str = "<main>
+<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>
+</main>";
+
+    /// A local stand-in for the site: answers `requests` requests with `body`,
+    /// then hands back everything it was sent. Returns its base URL.
+    fn stub_server(body: &'static str, requests: usize) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut seen = Vec::new();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                // Headers, then however much body they announce
+                loop {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length: usize = text[..end]
+                            .lines()
+                            .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|raw| raw.trim().parse().unwrap()))
+                            .unwrap_or(0);
+                        if request.len() >= end + 4 + length || read == 0 {
+                            break;
+                        }
+                    }
+                }
+                write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body).unwrap();
+                seen.push(String::from_utf8_lossy(&request).into_owned());
+            }
+            seen
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn parses_right_answer() {
+        assert_eq!(Submission::parse(RIGHT_ANSWER), Submission { outcome: Outcome::Correct, wait: None });
+    }
+
+    #[test]
+    fn parses_too_high_and_too_low() {
+        assert_eq!(
+            Submission::parse(TOO_HIGH),
+            Submission { outcome: Outcome::TooHigh, wait: Some(Duration::from_secs(60)) }
+        );
+        assert_eq!(
+            Submission::parse(TOO_LOW),
+            Submission { outcome: Outcome::TooLow, wait: Some(Duration::from_secs(300)) }
+        );
+        assert_eq!(Submission::parse(WRONG).outcome, Outcome::Wrong);
+    }
+
+    #[test]
+    fn parses_time_left_to_wait() {
+        assert_eq!(
+            Submission::parse(TOO_RECENT),
+            Submission { outcome: Outcome::TooRecent, wait: Some(Duration::from_secs(4 * 60 + 12)) }
+        );
+        assert_eq!(parse_wait("You have 7s left to wait."), Some(Duration::from_secs(7)));
+        assert_eq!(parse_wait("Nothing to see here"), None);
+    }
+
+    #[test]
+    fn parses_wrong_level() {
+        assert_eq!(Submission::parse(WRONG_LEVEL), Submission { outcome: Outcome::WrongLevel, wait: None });
+    }
+
+    #[test]
+    fn keeps_unrecognized_text() {
+        let submission = Submission::parse("<article><p>Something <em>new</em>.</p></article>");
+        assert_eq!(submission.outcome, Outcome::Unrecognized(String::from("Something new.")));
+    }
+
+    #[test]
+    fn outcomes_map_to_facts() {
+        let judged = |outcome: Outcome| Submission { outcome, wait: None };
+        let num = Answer::Num(42);
+        let text = Answer::from("abc");
+
+        assert_eq!(judged(Outcome::Correct).fact(&num), Some(Fact::Correct(num.clone())));
+        assert_eq!(judged(Outcome::TooHigh).fact(&num), Some(Fact::TooHigh(42)));
+        assert_eq!(judged(Outcome::TooLow).fact(&num), Some(Fact::TooLow(42)));
+        assert_eq!(judged(Outcome::Wrong).fact(&num), Some(Fact::Wrong(num.clone())));
+        // A hint about a text answer can't be kept as a bound
+        assert_eq!(judged(Outcome::TooHigh).fact(&text), Some(Fact::Wrong(text.clone())));
+        // Nothing was judged, so there is nothing to remember
+        assert_eq!(judged(Outcome::TooRecent).fact(&num), None);
+        assert_eq!(judged(Outcome::WrongLevel).fact(&num), None);
+        assert_eq!(judged(Outcome::Unrecognized(String::from("?"))).fact(&num), None);
+    }
+
+    #[test]
+    fn submits_answer_form_to_the_site() {
+        let (base_url, server) = stub_server(TOO_LOW, 1);
+        let submission = Client::new(&base_url, "secret").submit_answer(2024, 6, Part::Two, &Answer::Num(1913));
+        assert_eq!(submission, Ok(Submission { outcome: Outcome::TooLow, wait: Some(Duration::from_secs(300)) }));
+        assert_eq!(submission.unwrap().fact(&Answer::Num(1913)), Some(Fact::TooLow(1913)));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/6/answer "));
+        assert!(requests[0].ends_with("level=2&answer=1913"));
+    }
+
+    #[test]
+    fn cached_input_is_never_downloaded_again() {
+        let path = temp_dir("client_cached").join("day1_input.txt");
+        fs::write(&path, "3   4\n").unwrap();
+
+        let fetched = fetch_cached(&path, || panic!("a cached input must not be downloaded"), 2024, 1);
+        assert_eq!(fetched, Ok(false));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3   4\n");
+    }
+
+    #[test]
+    fn downloads_missing_input_once_with_user_agent() {
+        let (base_url, server) = stub_server("3   4\n4   3\n", 1);
+        let path = temp_dir("client_download").join("inputs/day1_input.txt");
+
+        let fetched = fetch_cached(&path, || Ok(Client::new(&base_url, "secret")), 2024, 1);
+        assert_eq!(fetched, Ok(true));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3   4\n4   3\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2024/day/1/input "));
+        assert!(requests[0].to_ascii_lowercase().contains(&format!("user-agent: {}", USER_AGENT.to_ascii_lowercase())));
+        assert!(requests[0].contains("session=secret"));
+
+        // The server is gone now, so a second download would fail
+        let again = fetch_cached(&path, || Ok(Client::new(&base_url, "secret")), 2024, 1);
+        assert_eq!(again, Ok(false));
+    }
+}
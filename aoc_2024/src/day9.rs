@@ -1,26 +1,48 @@
-use core::fmt;
-use std::{ collections::{ HashMap, HashSet }, ops::Deref, path::Display, slice::Iter };
+use crate::error::{ AocError, AocResult };
+use crate::solution::{ Answer, Solution };
 
-use itertools::Itertools;
+type Num = usize;
 
-use crate::utils;
+#[derive(Default)]
+pub struct Day9 {
+    input: Vec<Num>,
+}
 
-type Num = usize;
+impl Solution for Day9 {
+    fn parse(&mut self, input: &str) -> AocResult<()> {
+        // This problem only has 1 line of input
+        let line = input.lines().next().filter(|line| !line.is_empty()).ok_or_else(|| AocError::new("the disk map is empty"))?;
+        self.input = line
+            .chars()
+            .enumerate()
+            .map(|(column_index, item)| {
+                item.to_digit(10)
+                    .map(|digit| digit as Num)
+                    .ok_or_else(|| AocError::at(0, column_index, line, format!("expected a digit, got '{}'", item)))
+            })
+            .collect::<AocResult<_>>()?;
+        Ok(())
+    }
 
-pub fn part1() {
-    // This problem only has 1 line of input
-    let input: Vec<Num> = utils
-        ::read_input_as_lines("day9_input.txt")
-        .first()
-        .unwrap()
-        .chars()
-        .map(|item| item.to_string().parse::<Num>().unwrap())
-        .collect();
+    fn examples(&self) -> &'static [&'static str] {
+        &[include_str!("../input/day9_example1.txt")]
+    }
 
+    fn part1(&self) -> AocResult<Answer> {
+        Ok(compact_blocks(&self.input).into())
+    }
+
+    fn part2(&self) -> AocResult<Answer> {
+        Ok(compact_files(&self.input).into())
+    }
+}
+
+fn compact_blocks(input: &[Num]) -> Num {
     let mut converted: Vec<Option<Num>> = input
         .chunks(2)
         .enumerate()
-        .map(|(chunk_id, chunk)| {
+        .flat_map(|(chunk_id, chunk)| {
+            // chunks() never hands out an empty slice, so the file length is always there
             let file_len = chunk[0];
             let free_space_len = chunk.get(1);
             let ids = vec![Some(chunk_id)].into_iter().cycle().take(file_len);
@@ -34,9 +56,7 @@ pub fn part1() {
             };
             result
         })
-        .flatten()
         .collect();
-    let original_converted = converted.clone();
 
     let ordered_items: Vec<Option<Num>> = converted
         .clone()
@@ -44,211 +64,68 @@ pub fn part1() {
         .enumerate()
         .map(|(index, item)| {
             match item {
-                Some(value) => *converted.get(index).unwrap(),
+                Some(_value) => *converted.get(index).unwrap(),
                 None => {
                     let mut converted_search = converted.iter();
                     match converted_search.rposition(|item| item.is_some()) {
-                        Some(rightmost_value_index) => {
+                        Some(rightmost_value_index)
                             // Super hacky, but:
                             // We know rposition index exists because its already evaluated as some,
                             // We know that the item at that position is Some because that's the predicate for rposition
                             // So, flatten and unwrap should never panic :fingers-crossed:
-                            if rightmost_value_index > index {
+                            if rightmost_value_index > index => {
                                 let rightmost_value: Num = converted
                                     .get(rightmost_value_index)
                                     .unwrap()
-                                    .deref()
                                     .unwrap();
                                 converted.swap(index, rightmost_value_index);
                                 Some(rightmost_value)
-                            } else {
-                                None
                             }
-                        }
                         _ => { None }
                     }
                 }
             }
         })
         .collect();
-    /*
-    converted.clone().iter().for_each(|item| {
-        match item {
-            Some(num) => print!("{}", num),
-            _ => print!(".")
-        }
-    });
-     */
-    original_converted.iter().for_each(|item| {
-        match item {
-            Some(num) => print!("{}", num),
-            _ => print!("."),
-        }
-    });
-    println!("");
-    ordered_items.iter().for_each(|item| {
-        match item {
-            Some(num) => print!("{}", num),
-            _ => print!("."),
-        }
-    });
-    println!("");
     let total: usize = ordered_items
         .iter()
         .flatten()
         .enumerate()
         .map(|(index, value)| { index * value })
         .sum();
-    println!("total sum: {}", total);
+    total
 }
 
-pub fn part2() {
-    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-    struct Fragment {
-        file_id: usize,
-        file_multiplier: usize,
-        free_space: usize,
-        consumed_free_space: usize,
-        moved: bool,
-        appended_fragments: Vec<Fragment>,
-    }
-
-    impl fmt::Display for Fragment {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            let x: String = (0..self.file_multiplier).map(|_| self.file_id.to_string()).join("");
-            write!(f, "{}", x)
+fn compact_files(input: &[Num]) -> Num {
+    // Where each file and each gap starts on the disk, and how long it is
+    let mut files: Vec<(Num, Num)> = Vec::new();
+    let mut gaps: Vec<(Num, Num)> = Vec::new();
+    let mut position = 0;
+    for (index, length) in input.iter().enumerate() {
+        if index % 2 == 0 {
+            files.push((position, *length));
+        } else {
+            gaps.push((position, *length));
         }
+        position += length;
     }
-    // This problem only has 1 line of input
-    let input: Vec<Num> = utils
-        ::read_input_as_lines("day9_input.txt")
-        .first()
-        .unwrap()
-        .chars()
-        .map(|item| item.to_string().parse::<Num>().unwrap())
-        .collect();
-
-    let mut converted: Vec<Fragment> = input
-        .chunks(2)
-        .enumerate()
-        .map(|(chunk_id, chunk)| {
-            let file_len = chunk[0];
-            println!("chunk: {:?}", chunk);
-            let free_space_len = match chunk.get(1) {
-                Some(l) => *l,
-                _ => 0,
-            };
-            Fragment {
-                file_id: chunk_id,
-                file_multiplier: file_len,
-                free_space: free_space_len,
-                consumed_free_space: 0,
-                moved: false,
-                appended_fragments: Vec::new(),
-            }
-        })
-        .collect();
-
-    let mut main_reference = converted.clone();
-    let mut insertions: HashMap<Num, Vec<Num>> = HashMap::new();
-    converted
-        .iter_mut()
-        .rev()
-        .enumerate()
-        .map(|(index, frag)| {
-            let open_spot = main_reference
-                .iter()
-                .position(|item| frag.file_multiplier <= item.consumed_free_space);
-            match open_spot {
-                Some(spot) => {
-                    let item = main_reference.get_mut(spot).unwrap();
-                    item.consumed_free_space += frag.file_multiplier;
-                    item.appended_fragments.push(frag.clone());
-
-                    let moved_frag = main_reference.get_mut(index).unwrap();
-                    moved_frag.moved = true;
-                }
-                None => (),
-            }
-        })
-        .collect::<Vec<_>>();
-    let result: Vec<Num> = main_reference.into_iter().flat_map(|frag| {
-        let followers = frag.appended_fragments
-            .into_iter()
-            .flat_map(|appended_frag| {
-                (0..appended_frag.file_multiplier).map(|_| appended_frag.file_id)
-            });
-        let leaders = (0..frag.file_multiplier).map(|_| frag.file_id);
-        leaders.chain(followers).collect::<Vec<usize>>()
-    }).collect();
 
-    
-    let total: usize = result
-        .into_iter()
-        .enumerate()
-        .map(|(index, value)| { index * value })
-        .sum();
-    println!("total sum: {}", total);
-
-    /* 
-    fn find_farthest_matches(
-        free_space: usize,
-        current_index: usize,
-        remainder: &mut Vec<Fragment>
-    ) -> Vec<Fragment> {
-        let farthest_index = remainder.iter().rposition(|item| !item.moved && (item.file_multiplier <= free_space));
-        match farthest_index {
-            Some(index) => {
-                if index < current_index {
-                    Vec::new()
-                } else {
-                    let item = remainder.remove(index);
-                    println!("removed item: {:?}", item);
-                    //remainder.iter().for_each(|item| { print!("{}", item)});
-                    //println!("");
-                    let remaining_amount = free_space - item.file_multiplier;
-                    vec![item]
-                        .into_iter()
-                        .chain(find_farthest_matches(remaining_amount, current_index, remainder))
-                        .collect()
-                }
-            }
-            None => { Vec::new() }
+    // Each file moves once, highest id first, into the leftmost gap that fits it and is to its left
+    for (file_start, file_len) in files.iter_mut().rev() {
+        let gap = gaps
+            .iter_mut()
+            .take_while(|(gap_start, _)| gap_start < file_start)
+            .find(|(_, gap_len)| gap_len >= file_len);
+        if let Some((gap_start, gap_len)) = gap {
+            *file_start = *gap_start;
+            *gap_start += *file_len;
+            *gap_len -= *file_len;
         }
     }
-    
-    let mut main_reference = converted.clone();
-    let mut moved_nodes: HashSet<Num> = HashSet::new();
-    let reordered: Vec<Num> = converted
-        .iter()
-        .enumerate()
-        .map(|(index, item)| {
-            let preprocessed = moved_nodes.get(&item.file_id);
-            match preprocessed {
-                Some(_) => Vec::new(),
-                None => {
-                    let matches = find_farthest_matches(item.free_space, index, &mut main_reference);
-                    let insertion: Vec<Fragment> = vec![item.clone()].into_iter().chain(matches.into_iter()).collect();
-                    insertion.iter().for_each(|item| {
-                        moved_nodes.insert(item.file_id);
-                    });
-                    // let ids = vec![Some(chunk_id)].into_iter().cycle().take(file_len);
-                    insertion.into_iter().map(|item| {
-                        vec![item.file_id].into_iter().cycle().take(item.file_multiplier)
-                    }).flatten().collect()
-                }
-            }
-        }).flatten().collect();
-
-    reordered.iter().for_each(|item| {
-        print!("{}", item);
-        });
 
-    let total: usize = reordered
-        .into_iter()
+    files
+        .iter()
         .enumerate()
-        .map(|(index, value)| { index * value })
-        .sum();
-    println!("total sum: {}", total);
-    */
+        .map(|(file_id, (start, length))| (*start..start + length).map(|block| block * file_id).sum::<Num>())
+        .sum()
 }
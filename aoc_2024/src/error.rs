@@ -0,0 +1,84 @@
+//! Errors from parsing or solving a puzzle.
+//! Where the problem is known, they point at the file, line and column of the input that caused it,
+//! so a malformed line shows up as that line rather than a panic somewhere inside a day.
+use std::{ error, fmt, path::{ Path, PathBuf }, str::FromStr };
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AocError {
+    pub message: String,
+    /// Filled in by whoever knows where the input came from; days only ever see the text.
+    pub file: Option<PathBuf>,
+    /// 1-based, like an editor would show it.
+    pub line: Option<usize>,
+    /// 1-based, counted in characters.
+    pub column: Option<usize>,
+    /// The offending line itself, shown under the message.
+    pub source_line: Option<String>,
+}
+
+pub type AocResult<T> = Result<T, AocError>;
+
+impl AocError {
+    /// An error with no particular place in the input, e.g. something missing altogether.
+    pub fn new(message: impl Into<String>) -> AocError {
+        AocError { message: message.into(), file: None, line: None, column: None, source_line: None }
+    }
+
+    /// An error about a whole line. `line_index` is 0-based, as it comes out of `enumerate`.
+    pub fn at_line(line_index: usize, line: &str, message: impl Into<String>) -> AocError {
+        AocError {
+            line: Some(line_index + 1),
+            source_line: Some(String::from(line)),
+            ..AocError::new(message)
+        }
+    }
+
+    /// An error about a spot within a line. `column_index` is a 0-based character offset.
+    pub fn at(line_index: usize, column_index: usize, line: &str, message: impl Into<String>) -> AocError {
+        AocError { column: Some(column_index + 1), ..AocError::at_line(line_index, line, message) }
+    }
+
+    /// Names the file the input came from, unless the error already names one.
+    pub fn in_file(mut self, path: &Path) -> AocError {
+        self.file.get_or_insert_with(|| path.to_path_buf());
+        self
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self.file.as_ref().map(|file| file.display().to_string());
+        let place: Vec<String> = [file, self.line.map(|line| line.to_string()), self.column.map(|column| column.to_string())]
+            .into_iter()
+            .flatten()
+            .collect();
+        if !place.is_empty() {
+            write!(f, "{}: ", place.join(":"))?;
+        }
+        write!(f, "{}", self.message)?;
+
+        if let (Some(line), Some(source_line)) = (self.line, &self.source_line) {
+            let gutter = line.to_string();
+            write!(f, "\n {} | {}", gutter, source_line)?;
+            if let Some(column) = self.column {
+                write!(f, "\n {} | {}^", " ".repeat(gutter.len()), " ".repeat(column - 1))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for AocError {}
+
+/// Parses the number in `field`, which must be a slice of `line`, pointing at the field if it isn't one.
+pub fn parse_number<T: FromStr>(line_index: usize, line: &str, field: &str) -> AocResult<T> {
+    field.parse::<T>().map_err(|_| {
+        // Where the field starts in the line, worked out from the slices themselves
+        let offset = (field.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| *offset <= line.len() && line.is_char_boundary(*offset))
+            .unwrap_or(0);
+        let column_index = line[..offset].chars().count();
+        AocError::at(line_index, column_index, line, format!("expected a number, got '{}'", field))
+    })
+}
@@ -0,0 +1,310 @@
+//! Benchmark results kept across runs, so a change to shared code can be checked for slowdowns.
+//! Every `bench` appends one line per day and stage to a tab separated file:
+//!     commit  unix_time  source  day  stage  runs  min_ns  median_ns  max_ns
+//! Stages are `parse`, `part1` and `part2`. Only medians are compared; min and max are kept for reference.
+use std::{ collections::BTreeMap, fmt, fs::{ self, OpenOptions }, io::Write, path::PathBuf, process, time::{ Duration, SystemTime, UNIX_EPOCH } };
+
+use colored::Colorize;
+
+use crate::bench::{ self, DayTimings, Stats };
+use crate::input::InputSource;
+
+/// The history file. Machine specific, so it stays out of git.
+pub fn history_file() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("bench_history.tsv")
+}
+
+/// The commit being benchmarked, marked `-dirty` when there are uncommitted changes
+/// so those numbers aren't mistaken for the commit's own.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        process::Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match (git(&["rev-parse", "--short", "HEAD"]), git(&["status", "--porcelain", "--untracked-files=no"])) {
+        (Some(commit), Some(status)) if !status.is_empty() => format!("{}-dirty", commit),
+        (Some(commit), _) => commit,
+        (None, _) => String::from("unknown"),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+
+    fn parse(raw: &str) -> Option<Stage> {
+        match raw {
+            "parse" => Some(Stage::Parse),
+            "part1" => Some(Stage::Part1),
+            "part2" => Some(Stage::Part2),
+            _ => None,
+        }
+    }
+
+    fn of(self, timings: &DayTimings) -> Stats {
+        match self {
+            Stage::Parse => timings.parse,
+            Stage::Part1 => timings.part1,
+            Stage::Part2 => timings.part2,
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part1 => write!(f, "part1"),
+            Stage::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub commit: String,
+    pub timestamp: u64,
+    pub source: String,
+    pub day: u8,
+    pub stage: Stage,
+    pub runs: usize,
+    pub stats: Stats,
+}
+
+impl Record {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.commit,
+            self.timestamp,
+            self.source,
+            self.day,
+            self.stage,
+            self.runs,
+            self.stats.min.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.max.as_nanos()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Record> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [commit, timestamp, source, day, stage, runs, min, median, max] = fields.as_slice() else {
+            return None;
+        };
+        let nanos = |raw: &str| raw.parse::<u64>().ok().map(Duration::from_nanos);
+        Some(Record {
+            commit: String::from(*commit),
+            timestamp: timestamp.parse().ok()?,
+            source: String::from(*source),
+            day: day.parse().ok()?,
+            stage: Stage::parse(stage)?,
+            runs: runs.parse().ok()?,
+            stats: Stats { min: nanos(min)?, median: nanos(median)?, max: nanos(max)? },
+        })
+    }
+}
+
+/// Appends a benchmark's results to the history file.
+pub fn save(timings: &[DayTimings], source: &InputSource) -> Result<String, String> {
+    let commit = current_commit();
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let lines: String = timings
+        .iter()
+        .flat_map(|day| {
+            Stage::ALL.into_iter().map(|stage| Record {
+                commit: commit.clone(),
+                timestamp,
+                source: source.to_string(),
+                day: day.day,
+                stage,
+                runs: day.runs,
+                stats: stage.of(day),
+            })
+        })
+        .map(|record| record.to_line() + "\n")
+        .collect();
+
+    let path = history_file();
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|err| format!("can't write {}: {}", path.display(), err))?;
+    Ok(commit)
+}
+
+/// Every record in the history file, oldest first. Lines that can't be read are skipped.
+pub fn load() -> Result<Vec<Record>, String> {
+    let path = history_file();
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents.lines().filter_map(Record::from_line).collect()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(format!("can't read {}: {}", path.display(), err)),
+    }
+}
+
+/// The latest median for each (day, stage) recorded against `commit` on `source`.
+fn latest_for(records: &[Record], commit: &str, source: &str) -> BTreeMap<(u8, Stage), Duration> {
+    records
+        .iter()
+        .filter(|record| record.commit == commit && record.source == source)
+        .map(|record| ((record.day, record.stage), record.stats.median))
+        .collect()
+}
+
+/// How one day and stage moved between two commits.
+#[derive(Clone, Debug)]
+pub struct Change {
+    pub day: u8,
+    pub stage: Stage,
+    pub before: Duration,
+    pub after: Duration,
+    /// Percent change in the median; positive is slower.
+    pub percent: f64,
+    /// Slower by more than the threshold.
+    pub regressed: bool,
+}
+
+pub struct Comparison {
+    pub current: String,
+    pub changes: Vec<Change>,
+}
+
+impl Comparison {
+    pub fn regressions(&self) -> usize {
+        self.changes.iter().filter(|change| change.regressed).count()
+    }
+}
+
+/// Compares `current` (or the most recently benchmarked commit) against `baseline`,
+/// for every day and stage both have numbers for on `source`.
+/// A stage regressed when it got slower by more than `threshold` percent.
+pub fn compare(records: &[Record], baseline: &str, current: Option<&str>, source: &str, threshold: u32) -> Result<Comparison, String> {
+    let current = match current {
+        Some(current) => String::from(current),
+        None => records
+            .iter()
+            .rev()
+            .find(|record| record.source == source && record.commit != baseline)
+            .map(|record| record.commit.clone())
+            .ok_or_else(|| format!("no benchmarks besides {} in {}", baseline, history_file().display()))?,
+    };
+
+    let before = latest_for(records, baseline, source);
+    let after = latest_for(records, &current, source);
+    if before.is_empty() {
+        return Err(format!("no benchmarks for {} on {} in {}", baseline, source, history_file().display()));
+    }
+
+    let changes = before
+        .into_iter()
+        .filter_map(|((day, stage), before)| {
+            let after = *after.get(&(day, stage))?;
+            let percent = (after.as_secs_f64() / before.as_secs_f64().max(f64::EPSILON) - 1.0) * 100.0;
+            Some(Change { day, stage, before, after, percent, regressed: percent > f64::from(threshold) })
+        })
+        .collect();
+    Ok(Comparison { current, changes })
+}
+
+/// Prints a comparison as a table, regressions in red.
+pub fn print_comparison(comparison: &Comparison, baseline: &str, source: &str, threshold: u32) {
+    println!(
+        "Comparing {} (baseline) to {} on {}, flagging slowdowns over {}%",
+        baseline, comparison.current, source, threshold
+    );
+    println!("{:>3}  {:<6}  {:>10}  {:>10}  {:>8}", "Day", "Stage", "Baseline", "Current", "Change");
+    for change in &comparison.changes {
+        let change_cell = format!("{:>+7.1}%", change.percent);
+        let change_cell = match change.regressed {
+            true => change_cell.red().to_string(),
+            false => change_cell,
+        };
+        println!(
+            "{:>3}  {:<6}  {:>10}  {:>10}  {}",
+            change.day,
+            change.stage,
+            bench::format_duration(change.before),
+            bench::format_duration(change.after),
+            change_cell
+        );
+    }
+    println!("{} stage(s) slower than the threshold", comparison.regressions());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, day: u8, stage: Stage, median_ms: u64) -> Record {
+        let median = Duration::from_millis(median_ms);
+        Record {
+            commit: String::from(commit),
+            timestamp: 1_733_000_000,
+            source: String::from("input"),
+            day,
+            stage,
+            runs: 10,
+            stats: Stats { min: median / 2, median, max: median * 2 },
+        }
+    }
+
+    #[test]
+    fn records_survive_a_round_trip() {
+        let original = record("abc1234-dirty", 7, Stage::Part2, 12);
+        let line = original.to_line();
+        assert_eq!(line, "abc1234-dirty\t1733000000\tinput\t7\tpart2\t10\t6000000\t12000000\t24000000");
+        let read = Record::from_line(&line).unwrap();
+        assert_eq!(read.to_line(), line);
+        assert_eq!((read.day, read.stage, read.stats.median), (7, Stage::Part2, Duration::from_millis(12)));
+
+        assert!(Record::from_line("abc1234\t1733000000\tinput\t7\tpart3\t10\t1\t2\t3").is_none());
+        assert!(Record::from_line("abc1234\t1733000000\tinput").is_none());
+    }
+
+    #[test]
+    fn latest_record_wins() {
+        let records = [
+            record("old", 1, Stage::Parse, 10),
+            record("new", 1, Stage::Parse, 30),
+            record("old", 1, Stage::Parse, 20),
+        ];
+        let latest = latest_for(&records, "old", "input");
+        assert_eq!(latest.into_iter().collect::<Vec<_>>(), vec![((1, Stage::Parse), Duration::from_millis(20))]);
+        assert!(latest_for(&records, "old", "example 1").is_empty());
+    }
+
+    #[test]
+    fn only_slowdowns_over_the_threshold_are_flagged() {
+        let records = [
+            record("old", 1, Stage::Parse, 100),
+            record("old", 1, Stage::Part1, 100),
+            record("old", 1, Stage::Part2, 100),
+            record("old", 2, Stage::Parse, 100),
+            record("new", 1, Stage::Parse, 105),
+            record("new", 1, Stage::Part1, 111),
+            record("new", 1, Stage::Part2, 50),
+        ];
+        let comparison = compare(&records, "old", None, "input", 10).unwrap();
+        assert_eq!(comparison.current, "new");
+        // Day 2 was only benchmarked on the baseline, so there is nothing to compare it to
+        let flagged: Vec<(Stage, bool)> = comparison.changes.iter().map(|change| (change.stage, change.regressed)).collect();
+        assert_eq!(flagged, vec![(Stage::Parse, false), (Stage::Part1, true), (Stage::Part2, false)]);
+        assert_eq!(comparison.regressions(), 1);
+
+        assert!(compare(&records, "missing", Some("new"), "input", 10).is_err());
+    }
+}
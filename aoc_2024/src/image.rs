@@ -0,0 +1,248 @@
+//! Saving grids as pictures, which are far easier to read than terminal output once a grid is
+//! more than a screen wide, and can be attached to a code review.
+//! Colors come from a `render::Renderer`, so the same symbol and layer styles serve both:
+//! a cell is filled with its background color, or its foreground color if it only has one, or white.
+//! SVG additionally writes each cell's glyph on top. PPM and SVG need nothing extra;
+//! PNG needs the `png` feature.
+use std::{ fmt, fs, path::Path };
+
+use crate::render::{ Color, Renderer, Style };
+use crate::utils::{ Coord, Grid };
+
+type Rgb = [u8; 3];
+
+const WHITE: Rgb = [255, 255, 255];
+const BLACK: Rgb = [0, 0, 0];
+
+/// The usual xterm values for the named colors.
+fn rgb(color: Color) -> Rgb {
+    match color {
+        Color::Black => [0, 0, 0],
+        Color::Red => [205, 0, 0],
+        Color::Green => [0, 205, 0],
+        Color::Yellow => [205, 205, 0],
+        Color::Blue => [0, 0, 238],
+        Color::Magenta => [205, 0, 205],
+        Color::Cyan => [0, 205, 205],
+        Color::White => [229, 229, 229],
+        Color::BrightBlack => [127, 127, 127],
+        Color::BrightRed => [255, 0, 0],
+        Color::BrightGreen => [0, 255, 0],
+        Color::BrightYellow => [255, 255, 0],
+        Color::BrightBlue => [92, 92, 255],
+        Color::BrightMagenta => [255, 0, 255],
+        Color::BrightCyan => [0, 255, 255],
+        Color::BrightWhite => [255, 255, 255],
+        Color::TrueColor { r, g, b } => [r, g, b],
+    }
+}
+
+fn fill(style: Style) -> Rgb {
+    style.bg.or(style.fg).map_or(WHITE, rgb)
+}
+
+fn hex([r, g, b]: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Every visible cell's fill color, row by row, along with the visible width and height.
+fn fills<T: fmt::Display, P: fmt::Display>(renderer: &Renderer<P>, grid: &Grid<T>) -> (Vec<Rgb>, usize, usize) {
+    let (columns, rows) = renderer.visible(grid);
+    let (width, height) = (columns.len(), rows.len());
+    let colors = rows
+        .flat_map(|y| columns.clone().map(move |x| Coord::from_index(x, y)))
+        .map(|position| fill(renderer.cell(grid, position).1))
+        .collect();
+    (colors, width, height)
+}
+
+/// Raw RGB pixels with each cell drawn as a `scale` by `scale` square, plus the image width and height.
+fn pixels<T: fmt::Display, P: fmt::Display>(renderer: &Renderer<P>, grid: &Grid<T>, scale: usize) -> (Vec<u8>, usize, usize) {
+    let scale = scale.max(1);
+    let (colors, width, height) = fills(renderer, grid);
+    let mut bytes = Vec::with_capacity(width * height * scale * scale * 3);
+    for row in colors.chunks(width.max(1)) {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|color| std::iter::repeat_n(color, scale).flatten().copied())
+            .collect();
+        for _ in 0..scale {
+            bytes.extend_from_slice(&line);
+        }
+    }
+    (bytes, width * scale, height * scale)
+}
+
+/// The grid as a binary PPM (P6) image. Every image viewer and converter reads these.
+pub fn to_ppm<T: fmt::Display, P: fmt::Display>(renderer: &Renderer<P>, grid: &Grid<T>, scale: usize) -> Vec<u8> {
+    let (pixels, width, height) = pixels(renderer, grid, scale);
+    let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    image.extend(pixels);
+    image
+}
+
+/// The grid as an SVG image with cells `scale` pixels wide, each showing its glyph over its fill.
+pub fn to_svg<T: fmt::Display, P: fmt::Display>(renderer: &Renderer<P>, grid: &Grid<T>, scale: usize) -> String {
+    let (columns, rows) = renderer.visible(grid);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"monospace\" font-size=\"0.8\" text-anchor=\"middle\">\n",
+        columns.len() * scale.max(1),
+        rows.len() * scale.max(1),
+        columns.len(),
+        rows.len()
+    );
+    for (row, y) in rows.enumerate() {
+        for (column, x) in columns.clone().enumerate() {
+            let (glyph, style) = renderer.cell(grid, Coord::from_index(x, y));
+            svg += &format!("<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>", column, row, hex(fill(style)));
+            if !glyph.trim().is_empty() {
+                // With only a foreground color the cell is already filled with it, so the glyph goes black
+                let ink = match style {
+                    Style { fg: Some(fg), bg: Some(_) } => rgb(fg),
+                    _ => BLACK,
+                };
+                let glyph = glyph.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+                svg += &format!("<text x=\"{}.5\" y=\"{}.8\" fill=\"{}\">{}</text>", column, row, hex(ink), glyph);
+            }
+            svg += "\n";
+        }
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+/// The grid as a PNG image. Only compression can fail.
+#[cfg(feature = "png")]
+pub fn to_png<T: fmt::Display, P: fmt::Display>(renderer: &Renderer<P>, grid: &Grid<T>, scale: usize) -> std::io::Result<Vec<u8>> {
+    use std::io::Write;
+
+    let (pixels, width, height) = pixels(renderer, grid, scale);
+    let chunk = |image: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]| {
+        image.extend((data.len() as u32).to_be_bytes());
+        let start = image.len();
+        image.extend(kind);
+        image.extend(data);
+        let crc = crc32fast::hash(&image[start..]);
+        image.extend(crc.to_be_bytes());
+    };
+
+    let mut header = Vec::new();
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filtering and no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    // Each scanline starts with its filter type, 0 for none
+    let scanlines: Vec<u8> = pixels.chunks((width * 3).max(1)).flat_map(|line| [&[0], line].concat()).collect();
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&scanlines)?;
+    let data = encoder.finish()?;
+
+    let mut image = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    chunk(&mut image, b"IHDR", &header);
+    chunk(&mut image, b"IDAT", &data);
+    chunk(&mut image, b"IEND", &[]);
+    Ok(image)
+}
+
+/// Writes the grid to `path` in the format its extension names, in any case: `.ppm`, `.svg`, or `.png` with the `png` feature.
+pub fn save<T: fmt::Display, P: fmt::Display>(renderer: &Renderer<P>, grid: &Grid<T>, scale: usize, path: &Path) -> Result<(), String> {
+    let extension = path.extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase);
+    let contents = match extension.as_deref() {
+        Some("ppm") => to_ppm(renderer, grid, scale),
+        Some("svg") => to_svg(renderer, grid, scale).into_bytes(),
+        #[cfg(feature = "png")]
+        Some("png") => to_png(renderer, grid, scale).map_err(|err| format!("can't encode {}: {}", path.display(), err))?,
+        #[cfg(not(feature = "png"))]
+        Some("png") => return Err(format!("can't save {}: PNG needs the png feature (cargo run --features png)", path.display())),
+        _ => return Err(format!("can't tell what kind of image {} should be", path.display())),
+    };
+    fs::write(path, contents).map_err(|err| format!("can't write {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_dir;
+
+    fn styled() -> Renderer<'static> {
+        Renderer::new().color(false).symbol('#', Style::fg(Color::Red)).symbol('o', Style::fg(Color::Black).on(Color::Blue))
+    }
+
+    #[test]
+    fn ppm_has_a_header_and_three_bytes_per_pixel() {
+        let grid: Grid<char> = "#.o\n...".parse().unwrap();
+        let image = to_ppm(&styled(), &grid, 2);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 6 * 4 * 3);
+
+        let pixels = &image[header.len()..];
+        // Foreground only fills with the foreground, a background wins, plain cells are white
+        assert_eq!(&pixels[0..6], &[205, 0, 0, 205, 0, 0]);
+        assert_eq!(&pixels[6..9], &WHITE);
+        assert_eq!(&pixels[12..15], &[0, 0, 238]);
+        // Each row of cells is repeated `scale` times
+        assert_eq!(pixels[..18], pixels[18..36]);
+    }
+
+    #[test]
+    fn ppm_follows_the_viewport() {
+        let grid: Grid<char> = "#.o\n...".parse().unwrap();
+        let image = to_ppm(&styled().viewport(Coord::new(1, 0), 5, 1), &grid, 1);
+        assert_eq!(&image[..11], b"P6\n2 1\n255\n");
+        assert_eq!(image.len(), 11 + 2 * 3);
+    }
+
+    #[test]
+    fn svg_has_a_rect_per_cell() {
+        let grid: Grid<char> = "#.o\n..<".parse().unwrap();
+        let svg = to_svg(&styled(), &grid, 10);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\" viewBox=\"0 0 3 2\""));
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(svg.contains("fill=\"#cd0000\""));
+        assert!(svg.contains(">&lt;</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn save_reads_extensions_in_any_case() {
+        let dir = temp_dir("image_save");
+        let grid: Grid<char> = "#.".parse().unwrap();
+        for name in ["grid.ppm", "GRID.PPM", "grid.Svg"] {
+            let path = dir.join(name);
+            save(&styled(), &grid, 1, &path).unwrap();
+            assert!(fs::metadata(&path).unwrap().len() > 0, "{}", name);
+        }
+        assert_eq!(fs::read(dir.join("GRID.PPM")).unwrap(), to_ppm(&styled(), &grid, 1));
+
+        let error = save(&styled(), &grid, 1, &dir.join("grid.gif")).unwrap_err();
+        assert!(error.starts_with("can't tell what kind of image"), "{}", error);
+        assert!(save(&styled(), &grid, 1, &dir.join("grid")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(not(feature = "png"))]
+    #[test]
+    fn png_asks_for_the_feature() {
+        let dir = temp_dir("image_no_png");
+        let grid: Grid<char> = "#.".parse().unwrap();
+        let path = dir.join("grid.PNG");
+        let error = save(&styled(), &grid, 1, &path).unwrap_err();
+        assert!(error.contains("png feature"), "{}", error);
+        assert!(!path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn png_is_written_with_the_feature() {
+        let dir = temp_dir("image_png");
+        let grid: Grid<char> = "#.".parse().unwrap();
+        let path = dir.join("grid.PNG");
+        save(&styled(), &grid, 1, &path).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), to_png(&styled(), &grid, 1).unwrap());
+        assert!(fs::read(&path).unwrap().starts_with(b"\x89PNG\r\n\x1a\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+//! Finding the text a solution should run against.
+//! By default that's the day's input file, but any file, stdin, or one of a day's
+//! embedded puzzle examples can be used instead.
+use std::{ env, fmt, fs, io::{ self, Read }, path::{ Path, PathBuf } };
+
+use aoc_2024::error::AocError;
+use aoc_2024::solution::Solution;
+
+/// Overrides where input files are looked up, e.g. to run another account's inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `dayN_input.txt` in the input directory.
+    #[default]
+    Default,
+    Path(PathBuf),
+    Stdin,
+    /// One of the day's embedded examples, numbered from 1 like the files they come from.
+    Example(usize),
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Default => write!(f, "input"),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Example(number) => write!(f, "example {}", number),
+        }
+    }
+}
+
+pub struct Input {
+    pub text: String,
+    /// The file the text came from, which is also where its answers file sits next to.
+    /// Stdin has no file and therefore no known answers.
+    pub path: Option<PathBuf>,
+}
+
+impl Input {
+    /// Points an error from parsing or solving this input at where the input came from.
+    pub fn locate(&self, err: AocError) -> AocError {
+        match &self.path {
+            Some(path) => err.in_file(path),
+            None => err.in_file(Path::new("<stdin>")),
+        }
+    }
+}
+
+/// The directory holding input files.
+/// Resolved from the crate rather than the working directory, so the binary can be run from anywhere.
+pub fn input_dir() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("input"),
+    }
+}
+
+pub fn default_input_path(day: u8) -> PathBuf {
+    input_dir().join(format!("day{}_input.txt", day))
+}
+
+/// Examples are compiled in from this crate, so their answers live here too, whatever `AOC_INPUT_DIR` says.
+pub fn example_path(day: u8, number: usize) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("input").join(format!("day{}_example{}.txt", day, number))
+}
+
+/// Reads the input for `day` from `source`.
+/// Examples come from the solution itself, since they are compiled in.
+pub fn load(day: u8, source: &InputSource, solution: &dyn Solution) -> Result<Input, String> {
+    let read_file = |path: PathBuf| {
+        fs::read_to_string(&path)
+            .map(|text| Input { text, path: Some(path.clone()) })
+            .map_err(|err| format!("can't read input {}: {}", path.display(), err))
+    };
+
+    match source {
+        InputSource::Default => read_file(default_input_path(day)),
+        InputSource::Path(path) => read_file(path.clone()),
+        InputSource::Stdin => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|err| format!("can't read input from stdin: {}", err))?;
+            Ok(Input { text, path: None })
+        }
+        InputSource::Example(number) => {
+            let examples = solution.examples();
+            let text = number
+                .checked_sub(1)
+                .and_then(|index| examples.get(index))
+                .ok_or_else(|| format!("day {} has {} example(s), no example {}", day, examples.len(), number))?;
+            Ok(Input { text: String::from(*text), path: Some(example_path(day, *number)) })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_2024::answers::{ AnswerStore, Verdict };
+    use aoc_2024::registry::{ self, YEAR };
+    use aoc_2024::solution::Part;
+
+    use super::*;
+
+    fn check_day(day: u8, source: &InputSource) {
+        let mut solution = registry::lookup(YEAR, day).unwrap();
+        let input = load(day, source, solution.as_ref()).unwrap();
+        let answers = AnswerStore::load_for_input(input.path.as_ref().unwrap()).unwrap();
+        solution.parse(&input.text).unwrap();
+        for part in Part::BOTH {
+            let answer = match solution.solve(part) {
+                Ok(answer) => answer,
+                // A freshly scaffolded day
+                Err(err) if err.message == "not solved yet" => continue,
+                Err(err) => panic!("day {} part {} on {}: {}", day, part, source, err),
+            };
+            if let Verdict::Fail(reason) = answers.check(part, &answer) {
+                panic!("day {} part {} on {} gave {}: {}", day, part, source, answer, reason);
+            }
+        }
+    }
+
+    #[test]
+    fn every_day_matches_its_recorded_answers() {
+        for day in registry::days(YEAR) {
+            check_day(day, &InputSource::Default);
+        }
+    }
+
+    #[test]
+    fn every_example_matches_its_recorded_answers() {
+        for day in registry::days(YEAR) {
+            let examples = registry::lookup(YEAR, day).unwrap().examples().len();
+            assert!(examples > 0, "day {} has no examples", day);
+            for number in 1..=examples {
+                check_day(day, &InputSource::Example(number));
+            }
+        }
+    }
+
+    #[test]
+    fn loads_a_given_file_along_with_its_path() {
+        let day1 = registry::lookup(YEAR, 1).unwrap();
+        let path = example_path(1, 1);
+        let input = load(1, &InputSource::Path(path.clone()), day1.as_ref()).unwrap();
+        assert_eq!(input.text, day1.examples()[0]);
+        assert_eq!(input.path, Some(path));
+
+        let missing = load(1, &InputSource::Path(PathBuf::from("no/such/file.txt")), day1.as_ref());
+        assert!(missing.is_err_and(|err| err.starts_with("can't read input no/such/file.txt")));
+    }
+
+    #[test]
+    fn examples_are_numbered_from_one() {
+        let day3 = registry::lookup(YEAR, 3).unwrap();
+        let second = load(3, &InputSource::Example(2), day3.as_ref()).unwrap();
+        assert_eq!(second.text, day3.examples()[1]);
+        assert_eq!(second.path, Some(example_path(3, 2)));
+
+        for number in [0, 3] {
+            let error = load(3, &InputSource::Example(number), day3.as_ref()).err();
+            assert_eq!(error, Some(format!("day 3 has 2 example(s), no example {}", number)));
+        }
+    }
+}
@@ -0,0 +1,435 @@
+//! Named annotations laid over a grid without touching its cells: where a guard has been and which way
+//! it was facing, how often each cell was visited, which cells are antinodes, or any other value per cell.
+//! Each layer holds one kind of annotation, is created the first time it's written to,
+//! and can be queried, counted and cleared without disturbing the others.
+use std::{ collections::{ BTreeMap, HashMap, HashSet }, fmt };
+
+use crate::utils::{ Coord, Direction, Edges, Grid };
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Layer<P> {
+    /// Cells that are simply on, e.g. visited.
+    Marks(HashSet<Coord>),
+    /// How many times something happened at each cell.
+    Counts(HashMap<Coord, usize>),
+    /// Which ways something passed through each cell.
+    Directions(HashMap<Coord, HashSet<Direction>>),
+    /// Anything else, one value per cell.
+    Payloads(HashMap<Coord, P>),
+}
+
+impl<P> Layer<P> {
+    fn kind(&self) -> &'static str {
+        match self {
+            Layer::Marks(_) => "marks",
+            Layer::Counts(_) => "counts",
+            Layer::Directions(_) => "directions",
+            Layer::Payloads(_) => "payloads",
+        }
+    }
+
+    /// Number of cells with an annotation.
+    pub fn len(&self) -> usize {
+        match self {
+            Layer::Marks(cells) => cells.len(),
+            Layer::Counts(cells) => cells.len(),
+            Layer::Directions(cells) => cells.len(),
+            Layer::Payloads(cells) => cells.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, position: Coord) -> bool {
+        match self {
+            Layer::Marks(cells) => cells.contains(&position),
+            Layer::Counts(cells) => cells.contains_key(&position),
+            Layer::Directions(cells) => cells.contains_key(&position),
+            Layer::Payloads(cells) => cells.contains_key(&position),
+        }
+    }
+
+    /// Every annotated cell, in no particular order.
+    pub fn positions(&self) -> Vec<Coord> {
+        match self {
+            Layer::Marks(cells) => cells.iter().copied().collect(),
+            Layer::Counts(cells) => cells.keys().copied().collect(),
+            Layer::Directions(cells) => cells.keys().copied().collect(),
+            Layer::Payloads(cells) => cells.keys().copied().collect(),
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Layer::Marks(cells) => cells.clear(),
+            Layer::Counts(cells) => cells.clear(),
+            Layer::Directions(cells) => cells.clear(),
+            Layer::Payloads(cells) => cells.clear(),
+        }
+    }
+}
+
+/// The layers over one `width` by `height` grid. Positions off that grid are never annotated:
+/// writes to them are ignored and report that nothing changed, like `Grid::set` does.
+/// Over a wrapping grid every position is on it, and is stored as the cell it wraps to.
+/// `P` is the type of payload layers. It defaults to `char`, a symbol to draw in each cell,
+/// and must be `Display` for payloads to be drawn at all.
+#[derive(Clone, Debug)]
+pub struct Layers<P = char> {
+    width: usize,
+    height: usize,
+    edges: Edges,
+    layers: BTreeMap<String, Layer<P>>,
+}
+
+impl<P> Layers<P> {
+    pub fn new(width: usize, height: usize) -> Layers<P> {
+        Layers { width, height, edges: Edges::Bounded, layers: BTreeMap::new() }
+    }
+
+    /// No layers yet, sized to cover `grid` and wrapping around if it does.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Layers<P> {
+        Layers { edges: grid.edges(), ..Layers::new(grid.width(), grid.height()) }
+    }
+
+    /// The cell `position` annotates, following the same edge rules as `Grid::normalize`.
+    fn normalize(&self, position: Coord) -> Option<Coord> {
+        let on_grid = usize::try_from(position.x).is_ok_and(|x| x < self.width)
+            && usize::try_from(position.y).is_ok_and(|y| y < self.height);
+        match self.edges {
+            _ if on_grid => Some(position),
+            Edges::Wrapping if self.width > 0 && self.height > 0 => Some(position.wrapped(self.width, self.height)),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Layer<P>> {
+        self.layers.get(name)
+    }
+
+    /// The names of every layer, alphabetically.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.layers.keys().map(String::as_str)
+    }
+
+    /// The layer called `name`, created as `empty` if it doesn't exist yet.
+    /// Callers panic if it holds a different kind of annotation; that's a bug in the day, not in the input.
+    fn layer_mut(&mut self, name: &str, empty: Layer<P>) -> &mut Layer<P> {
+        self.layers.entry(String::from(name)).or_insert(empty)
+    }
+
+    fn kind_mismatch(name: &str, layer: &Layer<P>, wanted: &str) -> ! {
+        panic!("layer '{}' holds {}, not {}", name, layer.kind(), wanted)
+    }
+
+    /// Marks `position` in the marks layer `name`. Returns false if it was already marked or isn't on the grid.
+    pub fn mark(&mut self, name: &str, position: Coord) -> bool {
+        let Some(position) = self.normalize(position) else {
+            return false;
+        };
+        match self.layer_mut(name, Layer::Marks(HashSet::new())) {
+            Layer::Marks(cells) => cells.insert(position),
+            other => Self::kind_mismatch(name, other, "marks"),
+        }
+    }
+
+    pub fn is_marked(&self, name: &str, position: Coord) -> bool {
+        let Some(position) = self.normalize(position) else {
+            return false;
+        };
+        match self.get(name) {
+            Some(Layer::Marks(cells)) => cells.contains(&position),
+            Some(other) => Self::kind_mismatch(name, other, "marks"),
+            None => false,
+        }
+    }
+
+    /// Adds one to the count at `position` in the counts layer `name`, returning the new count.
+    /// Off the grid nothing is counted and 0 comes back.
+    pub fn increment(&mut self, name: &str, position: Coord) -> usize {
+        let Some(position) = self.normalize(position) else {
+            return 0;
+        };
+        match self.layer_mut(name, Layer::Counts(HashMap::new())) {
+            Layer::Counts(cells) => {
+                let count = cells.entry(position).or_default();
+                *count += 1;
+                *count
+            }
+            other => Self::kind_mismatch(name, other, "counts"),
+        }
+    }
+
+    /// The count at `position` in the counts layer `name`, 0 if nothing was counted there.
+    pub fn count_at(&self, name: &str, position: Coord) -> usize {
+        let Some(position) = self.normalize(position) else {
+            return 0;
+        };
+        match self.get(name) {
+            Some(Layer::Counts(cells)) => cells.get(&position).copied().unwrap_or(0),
+            Some(other) => Self::kind_mismatch(name, other, "counts"),
+            None => 0,
+        }
+    }
+
+    /// Records `direction` at `position` in the directions layer `name`.
+    /// Returns false if it was already recorded or isn't on the grid, which is how a walker notices it's in a loop.
+    pub fn add_direction(&mut self, name: &str, position: Coord, direction: Direction) -> bool {
+        let Some(position) = self.normalize(position) else {
+            return false;
+        };
+        match self.layer_mut(name, Layer::Directions(HashMap::new())) {
+            Layer::Directions(cells) => cells.entry(position).or_default().insert(direction),
+            other => Self::kind_mismatch(name, other, "directions"),
+        }
+    }
+
+    pub fn has_direction(&self, name: &str, position: Coord, direction: Direction) -> bool {
+        self.directions_at(name, position).is_some_and(|directions| directions.contains(&direction))
+    }
+
+    /// Every direction recorded at `position` in the directions layer `name`.
+    pub fn directions_at(&self, name: &str, position: Coord) -> Option<&HashSet<Direction>> {
+        let position = self.normalize(position)?;
+        match self.get(name) {
+            Some(Layer::Directions(cells)) => cells.get(&position),
+            Some(other) => Self::kind_mismatch(name, other, "directions"),
+            None => None,
+        }
+    }
+
+    /// Stores `value` at `position` in the payloads layer `name`, handing back what was there.
+    /// Off the grid nothing is stored and `None` comes back.
+    pub fn set_payload(&mut self, name: &str, position: Coord, value: P) -> Option<P> {
+        let position = self.normalize(position)?;
+        match self.layer_mut(name, Layer::Payloads(HashMap::new())) {
+            Layer::Payloads(cells) => cells.insert(position, value),
+            other => Self::kind_mismatch(name, other, "payloads"),
+        }
+    }
+
+    pub fn payload(&self, name: &str, position: Coord) -> Option<&P> {
+        let position = self.normalize(position)?;
+        match self.get(name) {
+            Some(Layer::Payloads(cells)) => cells.get(&position),
+            Some(other) => Self::kind_mismatch(name, other, "payloads"),
+            None => None,
+        }
+    }
+
+    /// Number of annotated cells in layer `name`, whatever its kind. 0 if there is no such layer.
+    pub fn count(&self, name: &str) -> usize {
+        self.get(name).map_or(0, Layer::len)
+    }
+
+    /// Empties layer `name`, keeping its kind.
+    pub fn clear(&mut self, name: &str) {
+        if let Some(layer) = self.layers.get_mut(name) {
+            layer.clear();
+        }
+    }
+
+    /// Drops layer `name` altogether, so it can be reused for a different kind of annotation.
+    pub fn remove(&mut self, name: &str) -> Option<Layer<P>> {
+        self.layers.remove(name)
+    }
+
+    /// Displays `grid` with the layers in `names` drawn over it.
+    /// Where several of them annotate a cell, the one listed first wins.
+    pub fn overlay<'a, T>(&'a self, grid: &'a Grid<T>, names: &'a [&'a str]) -> Overlay<'a, T, P> {
+        Overlay { grid, layers: self, names }
+    }
+}
+
+/// A grid with some of its layers drawn over it, see `Layers::overlay`.
+/// Marks show as `X`, counts as a digit (`+` past 9), a single direction as an arrow
+/// and several as `+`, and payloads as themselves. Other cells show the grid's own cell.
+pub struct Overlay<'a, T, P> {
+    grid: &'a Grid<T>,
+    layers: &'a Layers<P>,
+    names: &'a [&'a str],
+}
+
+/// How a single direction is drawn.
+fn arrow(direction: Direction) -> char {
+    match direction {
+        Direction::N => '^',
+        Direction::E => '>',
+        Direction::S => 'v',
+        Direction::W => '<',
+        Direction::NE | Direction::SW => '/',
+        Direction::NW | Direction::SE => '\\',
+    }
+}
+
+impl<P: fmt::Display> Layer<P> {
+    /// How this layer draws `position`, or `None` if it has nothing there. See `Overlay` for the glyphs.
+    pub fn glyph(&self, position: Coord) -> Option<String> {
+        match self {
+            Layer::Marks(cells) => cells.contains(&position).then(|| String::from("X")),
+            Layer::Counts(cells) => cells.get(&position).map(|count| match count {
+                0..=9 => count.to_string(),
+                _ => String::from("+"),
+            }),
+            Layer::Directions(cells) => cells.get(&position).map(|directions| match directions.len() {
+                1 => directions.iter().map(|direction| arrow(*direction)).collect(),
+                _ => String::from("+"),
+            }),
+            Layer::Payloads(cells) => cells.get(&position).map(|payload| payload.to_string()),
+        }
+    }
+}
+
+impl<T: fmt::Display, P: fmt::Display> fmt::Display for Overlay<'_, T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.grid.height() {
+            for (position, cell) in self.grid.row(y) {
+                let annotation = self.names.iter().find_map(|name| self.layers.get(name)?.glyph(position));
+                match annotation {
+                    Some(annotation) => write!(f, "{}", annotation)?,
+                    None => write!(f, "{}", cell)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_are_set_once() {
+        let mut layers: Layers = Layers::new(3, 2);
+        assert!(layers.mark("seen", Coord::new(0, 0)));
+        assert!(!layers.mark("seen", Coord::new(0, 0)));
+        assert!(layers.mark("seen", Coord::new(2, 1)));
+        assert!(!layers.mark("seen", Coord::new(3, 0)));
+        assert!(layers.is_marked("seen", Coord::new(2, 1)));
+        assert!(!layers.is_marked("seen", Coord::new(1, 1)));
+        assert_eq!(layers.count("seen"), 2);
+
+        layers.clear("seen");
+        assert_eq!(layers.count("seen"), 0);
+        assert!(!layers.is_marked("seen", Coord::new(0, 0)));
+        assert!(matches!(layers.get("seen"), Some(Layer::Marks(_))));
+    }
+
+    #[test]
+    fn counts_add_up() {
+        let mut layers: Layers = Layers::new(3, 2);
+        assert_eq!(layers.increment("visits", Coord::new(1, 0)), 1);
+        assert_eq!(layers.increment("visits", Coord::new(1, 0)), 2);
+        assert_eq!(layers.increment("visits", Coord::new(0, 1)), 1);
+        assert_eq!(layers.increment("visits", Coord::new(-1, 0)), 0);
+        assert_eq!(layers.count_at("visits", Coord::new(1, 0)), 2);
+        assert_eq!(layers.count_at("visits", Coord::new(2, 1)), 0);
+        assert_eq!(layers.count("visits"), 2);
+
+        layers.clear("visits");
+        assert_eq!(layers.count_at("visits", Coord::new(1, 0)), 0);
+        assert_eq!(layers.count("visits"), 0);
+    }
+
+    #[test]
+    fn directions_notice_repeats() {
+        let mut layers: Layers = Layers::new(3, 2);
+        assert!(layers.add_direction("path", Coord::new(1, 1), Direction::N));
+        assert!(layers.add_direction("path", Coord::new(1, 1), Direction::E));
+        assert!(!layers.add_direction("path", Coord::new(1, 1), Direction::N));
+        assert!(!layers.add_direction("path", Coord::new(1, 2), Direction::N));
+        assert!(layers.has_direction("path", Coord::new(1, 1), Direction::E));
+        assert!(!layers.has_direction("path", Coord::new(1, 1), Direction::S));
+        assert_eq!(layers.directions_at("path", Coord::new(1, 1)).map(HashSet::len), Some(2));
+        assert_eq!(layers.directions_at("path", Coord::new(0, 0)), None);
+        assert_eq!(layers.count("path"), 1);
+
+        layers.clear("path");
+        assert_eq!(layers.count("path"), 0);
+        assert!(layers.add_direction("path", Coord::new(1, 1), Direction::N));
+    }
+
+    #[test]
+    fn payloads_hold_anything() {
+        let mut layers: Layers<u32> = Layers::new(3, 2);
+        assert_eq!(layers.set_payload("cost", Coord::new(2, 0), 7), None);
+        assert_eq!(layers.set_payload("cost", Coord::new(2, 0), 9), Some(7));
+        assert_eq!(layers.set_payload("cost", Coord::new(2, 5), 1), None);
+        assert_eq!(layers.payload("cost", Coord::new(2, 0)), Some(&9));
+        assert_eq!(layers.payload("cost", Coord::new(2, 5)), None);
+        assert_eq!(layers.count("cost"), 1);
+
+        layers.clear("cost");
+        assert_eq!(layers.payload("cost", Coord::new(2, 0)), None);
+    }
+
+    #[test]
+    fn layers_are_independent() {
+        let mut layers: Layers<char> = Layers::new(3, 2);
+        layers.mark("seen", Coord::new(0, 0));
+        layers.increment("visits", Coord::new(0, 0));
+        layers.increment("visits", Coord::new(1, 0));
+        layers.add_direction("path", Coord::new(0, 0), Direction::S);
+        layers.set_payload("labels", Coord::new(2, 1), 'z');
+        assert_eq!(layers.names().collect::<Vec<_>>(), vec!["labels", "path", "seen", "visits"]);
+
+        layers.clear("visits");
+        assert_eq!(layers.count("visits"), 0);
+        assert_eq!(layers.count("seen"), 1);
+        assert_eq!(layers.count("path"), 1);
+        assert_eq!(layers.count("labels"), 1);
+
+        assert!(matches!(layers.remove("seen"), Some(Layer::Marks(_))));
+        assert_eq!(layers.count("seen"), 0);
+        // With the marks gone the name can hold another kind
+        assert_eq!(layers.increment("seen", Coord::new(0, 0)), 1);
+        assert_eq!(layers.count("nothing"), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "layer 'seen' holds marks, not counts")]
+    fn mixing_kinds_is_a_bug() {
+        let mut layers: Layers = Layers::new(3, 2);
+        layers.mark("seen", Coord::new(0, 0));
+        layers.increment("seen", Coord::new(0, 0));
+    }
+
+    #[test]
+    fn overlay_draws_the_first_layer_listed() {
+        let grid: Grid<char> = "...\n...".parse().unwrap();
+        let mut layers: Layers<char> = Layers::for_grid(&grid);
+        layers.mark("seen", Coord::new(0, 0));
+        layers.mark("seen", Coord::new(1, 0));
+        layers.add_direction("path", Coord::new(1, 0), Direction::E);
+        layers.increment("visits", Coord::new(2, 1));
+        layers.set_payload("labels", Coord::new(0, 1), 'z');
+        assert_eq!(layers.overlay(&grid, &["path", "seen", "visits", "labels"]).to_string(), "X>.\nz.1\n");
+    }
+
+    #[test]
+    fn wrapping_grids_annotate_the_cell_a_position_wraps_to() {
+        let grid: Grid<char> = "...\n...".parse::<Grid<char>>().unwrap().with_edges(Edges::Wrapping);
+        let mut layers: Layers<u32> = Layers::for_grid(&grid);
+        assert!(layers.mark("seen", Coord::new(-1, 0)));
+        assert!(!layers.mark("seen", Coord::new(2, 2)));
+        assert!(layers.is_marked("seen", Coord::new(2, 0)));
+        assert_eq!(layers.get("seen").map(Layer::positions), Some(vec![Coord::new(2, 0)]));
+
+        assert_eq!(layers.increment("visits", Coord::new(3, -1)), 1);
+        assert_eq!(layers.increment("visits", Coord::new(0, 1)), 2);
+        assert!(layers.add_direction("path", Coord::new(4, 3), Direction::E));
+        assert!(!layers.add_direction("path", Coord::new(1, 1), Direction::E));
+        assert_eq!(layers.set_payload("cost", Coord::new(-3, -2), 7), None);
+        assert_eq!(layers.payload("cost", Coord::new(0, 0)), Some(&7));
+
+        // Bounded layers still ignore the same positions
+        let mut bounded: Layers<u32> = Layers::for_grid(&grid.with_edges(Edges::Bounded));
+        assert!(!bounded.mark("seen", Coord::new(-1, 0)));
+        assert_eq!(bounded.increment("visits", Coord::new(3, -1)), 0);
+        assert_eq!(bounded.payload("cost", Coord::new(-3, -2)), None);
+    }
+}
@@ -0,0 +1,20 @@
+//! Advent of Code 2024 solutions, plus the toolkit they share.
+//! `utils` holds the grid, coordinate and direction types meant to be reused from year to year;
+//! `registry` is how the `aoc_2024` binary finds each day.
+pub mod answers;
+pub mod error;
+pub mod image;
+pub mod layers;
+pub mod pathfinding;
+pub mod regions;
+pub mod registry;
+pub mod render;
+pub mod solution;
+pub mod sparse;
+#[cfg(test)]
+mod testing;
+pub mod transform;
+pub mod utils;
+pub mod week1;
+pub mod day8;
+pub mod day9;
@@ -1,27 +1,237 @@
-mod utils;
-// mod week1;
-mod day8;
-mod day9;
-
-fn main() {
-    //println!("Day 1");
-    //week1::day1();
-    //println!("\nDay 2");
-    //week1::day2();
-    //println!("\nDay 3");
-    //week1::day3();
-    //println!("\nDay 4");
-    //week1::day4();
-    //week1::day4_part2();
-    //println!("\nDay 5");
-    //week1::day5();
-    //println!("\nDay 6");
-    //week1::day6();
-    // println!("\nDay 7");
-    // week1::day7();
-    // day8::part1();
-    // day8::part2();
-    // day9::part1();
-    day9::part2();
-    ()
+//! The command line front-end.
+//! The solutions and the toolkit they share live in the library; finding inputs, talking to the site,
+//! benchmarking and scaffolding live here, since they print and know where this checkout keeps its files.
+mod bench;
+mod cli;
+mod client;
+mod history;
+mod input;
+mod scaffold;
+#[cfg(test)]
+mod testing;
+
+use std::{ env, process::ExitCode, time::Duration };
+use aoc_2024::registry;
+use aoc_2024::answers::{ AnswerStore, Fact, Verdict };
+use aoc_2024::solution::{ Answer, Part };
+use cli::{ Command, Selection };
+use input::InputSource;
+
+/// Runs the selected parts of a day and checks them against the known answers.
+/// Returns how many parts failed their check.
+fn run_day(day: u8, part: Option<Part>, source: &InputSource) -> Result<usize, String> {
+    let mut solution = registry::lookup(registry::YEAR, day)
+        .ok_or_else(|| format!("no solution for day {}", day))?;
+    let input = input::load(day, source, solution.as_ref())?;
+    let answers = match &input.path {
+        Some(path) => Some(AnswerStore::load_for_input(path).map_err(|err| err.to_string())?),
+        None => None,
+    };
+    let (parsed, parse_time) = bench::time(|| solution.parse(&input.text));
+    parsed.map_err(|err| input.locate(err).to_string())?;
+    println!("Day {} Parse: {}", day, bench::format_duration(parse_time));
+
+    let mut failures = 0;
+    for current in Part::BOTH.into_iter().filter(|current| part.is_none_or(|part| part == *current)) {
+        let (answer, solve_time) = bench::time(|| solution.solve(current));
+        let answer = answer.map_err(|err| input.locate(err).to_string())?;
+        let verdict = answers
+            .as_ref()
+            .map_or(Verdict::Unknown, |answers| answers.check(current, &answer));
+        if let Verdict::Fail(_) = verdict {
+            failures += 1;
+        }
+        let took = bench::format_duration(solve_time);
+        match source {
+            InputSource::Default => println!("Day {} Part {}: {} [{}] in {}", day, current, answer, verdict, took),
+            source => println!("Day {} Part {} ({}): {} [{}] in {}", day, current, source, answer, verdict, took),
+        }
+    }
+    Ok(failures)
+}
+
+/// Runs every registered day, carrying on past days that fail.
+/// With `--example K`, days that have fewer than K examples are skipped.
+fn run_all(source: &InputSource) -> usize {
+    let mut failures = 0;
+    for day in registry::days(registry::YEAR) {
+        if let InputSource::Example(number) = source {
+            let examples = registry::lookup(registry::YEAR, day).map_or(0, |solution| solution.examples().len());
+            if examples < *number {
+                continue;
+            }
+        }
+        match run_day(day, None, source) {
+            Ok(day_failures) => failures += day_failures,
+            Err(err) => {
+                eprintln!("Day {}: error: {}", day, err);
+                failures += 1;
+            }
+        }
+    }
+    failures
+}
+
+fn run(selection: Selection, source: &InputSource) -> Result<usize, String> {
+    let failures = match selection {
+        Selection::Day(day, part) => run_day(day, part, source)?,
+        Selection::All => run_all(source),
+    };
+    if failures > 0 {
+        eprintln!("{} answer(s) failed verification", failures);
+    }
+    Ok(failures)
+}
+
+/// Benchmarks the selection, prints a summary table and adds the results to the history.
+/// Returns how many days went over budget.
+fn bench(selection: Selection, source: &InputSource, runs: usize, budget: Option<Duration>) -> Result<usize, String> {
+    let days = match selection {
+        Selection::Day(day, _) => vec![day],
+        Selection::All => registry::days(registry::YEAR),
+    };
+    let timings = days
+        .into_iter()
+        .map(|day| {
+            eprintln!("benchmarking day {} ({} runs)", day, runs);
+            bench::bench_day(day, runs, source)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let over_budget = bench::print_table(&timings, budget);
+    let commit = history::save(&timings, source)?;
+    println!("Saved to {} as {}", history::history_file().display(), commit);
+    Ok(over_budget)
+}
+
+/// Compares two benchmarked commits and returns how many stages got slower than `threshold` allows.
+fn compare(baseline: &str, against: Option<&str>, source: &InputSource, threshold: u32) -> Result<usize, String> {
+    let source = source.to_string();
+    let comparison = history::compare(&history::load()?, baseline, against, &source, threshold)?;
+    history::print_comparison(&comparison, baseline, &source, threshold);
+    Ok(comparison.regressions())
+}
+
+fn record(day: u8, part: Part, fact: Fact) -> Result<usize, String> {
+    let mut answers = AnswerStore::load_for_input(&input::default_input_path(day)).map_err(|err| err.to_string())?;
+    println!("Day {} Part {}: recorded {}", day, part, fact);
+    answers.record(part, fact);
+    answers.save().map_err(|err| err.to_string())?;
+    Ok(0)
+}
+
+fn new_day(day: u8) -> Result<usize, String> {
+    for path in scaffold::new_day(day)? {
+        println!("wrote {}", path.display());
+    }
+    println!("Day {} is ready; `fetch --day {}` once the puzzle unlocks, paste the example into its file", day, day);
+    Ok(0)
+}
+
+fn fetch(day: u8) -> Result<usize, String> {
+    let path = input::default_input_path(day);
+    match client::fetch_cached(&path, client::Client::from_env, registry::YEAR, day)? {
+        true => println!("Day {}: downloaded input to {}", day, path.display()),
+        false => println!("Day {}: input already cached at {}", day, path.display()),
+    }
+    Ok(0)
+}
+
+/// Solves a part against the default input and posts it, unless we already know how that would go.
+/// Returns 1 if the site did not accept the answer.
+fn submit(day: u8, part: Part, wait: bool) -> Result<usize, String> {
+    let mut solution = registry::lookup(registry::YEAR, day)
+        .ok_or_else(|| format!("no solution for day {}", day))?;
+    let path = input::default_input_path(day);
+    let input = input::load(day, &InputSource::Default, solution.as_ref())?;
+    let mut answers = AnswerStore::load_for_input(&path).map_err(|err| err.to_string())?;
+    solution.parse(&input.text).map_err(|err| input.locate(err).to_string())?;
+    let answer = solution.solve(part).map_err(|err| input.locate(err).to_string())?;
+
+    match answers.check(part, &answer) {
+        Verdict::Pass => {
+            println!("Day {} Part {}: {} is already known to be correct, not submitting", day, part, answer);
+            return Ok(0);
+        }
+        Verdict::Fail(reason) => {
+            return Err(format!("refusing to submit {}: {}", answer, reason));
+        }
+        Verdict::Unknown => (),
+    }
+
+    match (client::throttle_remaining(), wait) {
+        (Some(left), false) => {
+            return Err(format!("the site asked us to wait, {}s left (pass --wait to sit it out)", left.as_secs()));
+        }
+        (Some(_), true) => client::wait_for_throttle(),
+        (None, _) => (),
+    }
+
+    println!("Day {} Part {}: submitting {}", day, part, answer);
+    let submission = client::Client::from_env()?.submit_answer(registry::YEAR, day, part, &answer)?;
+    if let Some(wait) = submission.wait {
+        client::set_throttle(wait)?;
+    }
+    if let Some(fact) = submission.fact(&answer) {
+        answers.record(part, fact);
+        answers.save().map_err(|err| err.to_string())?;
+    }
+
+    match submission.outcome {
+        client::Outcome::Correct => {
+            println!("Day {} Part {}: {} is correct!", day, part, answer);
+            Ok(0)
+        }
+        client::Outcome::TooHigh => wrong(day, part, &answer, "too high", submission.wait),
+        client::Outcome::TooLow => wrong(day, part, &answer, "too low", submission.wait),
+        client::Outcome::Wrong => wrong(day, part, &answer, "wrong", submission.wait),
+        client::Outcome::TooRecent => Err(format!(
+            "submitted too soon, nothing was checked; {}s left to wait",
+            submission.wait.map_or(0, |wait| wait.as_secs())
+        )),
+        client::Outcome::WrongLevel => Err(String::from("that part is already solved, or not unlocked yet")),
+        client::Outcome::Unrecognized(text) => Err(format!("couldn't read the site's response: {}", text)),
+    }
+}
+
+fn wrong(day: u8, part: Part, answer: &Answer, how: &str, wait: Option<Duration>) -> Result<usize, String> {
+    println!("Day {} Part {}: {} is {}", day, part, answer, how);
+    if let Some(wait) = wait {
+        println!("the site asked us to wait {}s before trying again", wait.as_secs());
+    }
+    Ok(1)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(0)
+        }
+        Command::Run(selection, source) => run(selection, &source),
+        Command::Bench { selection, source, runs, budget } => bench(selection, &source, runs, budget),
+        Command::Compare { baseline, against, source, threshold } =>
+            compare(&baseline, against.as_deref(), &source, threshold),
+        Command::New { day } => new_day(day),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit { day, part, wait } => submit(day, part, wait),
+        Command::Record { day, part, fact } => record(day, part, fact),
+    };
+
+    match result {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,271 @@
+//! Shortest paths over a `Grid`, moving one orthogonal step at a time.
+//! Whether a step is allowed (and what it costs) is up to the caller, who is handed the cell being left
+//! and the cell being entered, so walls, one-way slopes and height limits all fit the same shape.
+//! Puzzles whose state is more than a position (facing, momentum, keys held) need their own search.
+use std::{ cmp::Reverse, collections::{ BinaryHeap, HashSet, VecDeque } };
+
+use crate::utils::{ Coord, Grid };
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path {
+    /// Total cost of the steps taken; the number of steps for BFS.
+    pub cost: usize,
+    /// Every cell visited, start and goal included.
+    pub cells: Vec<Coord>,
+}
+
+/// Everything a BFS or Dijkstra run learned about reaching each cell from `start`.
+#[derive(Clone, Debug)]
+pub struct Search {
+    pub start: Coord,
+    /// Cheapest cost to reach each cell, `None` where it can't be reached.
+    pub distances: Grid<Option<usize>>,
+    /// For each cell, every neighbor it can be reached from on some cheapest path.
+    predecessors: Grid<Vec<Coord>>,
+}
+
+impl Search {
+    fn new<T>(grid: &Grid<T>, start: Coord) -> Search {
+        let mut distances = grid.map(|_| None);
+        distances.set(start, Some(0));
+        Search { start, distances, predecessors: grid.map(|_| Vec::new()) }
+    }
+
+    /// Records that `to` can be reached from `from` at `cost`.
+    /// Returns true if that's cheaper than anything seen so far, i.e. `to` needs (re)visiting.
+    fn relax(&mut self, from: Coord, to: Coord, cost: usize) -> bool {
+        match self.distances.get(to).copied().flatten() {
+            Some(known) if known < cost => false,
+            Some(known) if known == cost => {
+                self.predecessors[to].push(from);
+                false
+            }
+            _ => {
+                self.distances.set(to, Some(cost));
+                self.predecessors[to] = vec![from];
+                true
+            }
+        }
+    }
+
+    pub fn distance(&self, goal: Coord) -> Option<usize> {
+        self.distances.get(goal).copied().flatten()
+    }
+
+    /// One cheapest path to `goal`, if it can be reached at all.
+    pub fn path_to(&self, goal: Coord) -> Option<Path> {
+        let cost = self.distance(goal)?;
+        let mut cells = vec![goal];
+        let mut current = goal;
+        while current != self.start {
+            current = self.predecessors[current][0];
+            cells.push(current);
+        }
+        cells.reverse();
+        Some(Path { cost, cells })
+    }
+
+    /// Every cheapest path to `goal`.
+    /// There can be exponentially many of them; `on_shortest_paths` is the cheap alternative
+    /// when only the cells involved matter.
+    pub fn all_paths_to(&self, goal: Coord) -> Vec<Path> {
+        let Some(cost) = self.distance(goal) else {
+            return Vec::new();
+        };
+        // Walk backwards from the goal, branching at every cell with more than one predecessor
+        let mut paths = Vec::new();
+        let mut pending = vec![vec![goal]];
+        while let Some(partial) = pending.pop() {
+            let current = partial[partial.len() - 1];
+            if current == self.start {
+                paths.push(Path { cost, cells: partial.into_iter().rev().collect() });
+                continue;
+            }
+            for previous in &self.predecessors[current] {
+                let mut longer = partial.clone();
+                longer.push(*previous);
+                pending.push(longer);
+            }
+        }
+        paths
+    }
+
+    /// Every cell that lies on at least one cheapest path to `goal`.
+    pub fn on_shortest_paths(&self, goal: Coord) -> HashSet<Coord> {
+        let mut cells = HashSet::new();
+        if self.distance(goal).is_none() {
+            return cells;
+        }
+        let mut pending = vec![goal];
+        while let Some(current) = pending.pop() {
+            if cells.insert(current) {
+                pending.extend(&self.predecessors[current]);
+            }
+        }
+        cells
+    }
+}
+
+/// Breadth-first search from `start`, where every allowed step costs 1.
+/// `passable(from, to)` decides whether a step between two neighboring cells is allowed.
+pub fn bfs<T>(grid: &Grid<T>, start: Coord, mut passable: impl FnMut(&T, &T) -> bool) -> Search {
+    let mut search = Search::new(grid, start);
+    if !grid.in_bounds(start) {
+        return search;
+    }
+
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        let next_cost = search.distance(current).unwrap_or(0) + 1;
+        for (_, neighbor, cell) in grid.neighbors4(current) {
+            if passable(&grid[current], cell) && search.relax(current, neighbor, next_cost) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm from `start`.
+/// `cost(from, to)` is the price of stepping between two neighboring cells, or `None` if that step isn't allowed.
+/// Steps should cost at least 1; free steps can make `all_paths_to` walk in circles.
+pub fn dijkstra<T>(grid: &Grid<T>, start: Coord, mut cost: impl FnMut(&T, &T) -> Option<usize>) -> Search {
+    let mut search = Search::new(grid, start);
+    if !grid.in_bounds(start) {
+        return search;
+    }
+
+    let mut queue = BinaryHeap::from([Reverse((0, start))]);
+    while let Some(Reverse((current_cost, current))) = queue.pop() {
+        if search.distance(current).is_some_and(|known| known < current_cost) {
+            // Already reached more cheaply since this entry was queued
+            continue;
+        }
+        for (_, neighbor, cell) in grid.neighbors4(current) {
+            let Some(step) = cost(&grid[current], cell) else {
+                continue;
+            };
+            if search.relax(current, neighbor, current_cost + step) {
+                queue.push(Reverse((current_cost + step, neighbor)));
+            }
+        }
+    }
+    search
+}
+
+/// A* from `start` to `goal`, stopping as soon as the goal is settled.
+/// `cost` works as for `dijkstra`. `heuristic(cell)` estimates the remaining cost to the goal and must never
+/// overestimate it, or the path found may not be the cheapest; Manhattan distance is right whenever every step costs at least 1.
+pub fn astar<T>(
+    grid: &Grid<T>,
+    start: Coord,
+    goal: Coord,
+    mut cost: impl FnMut(&T, &T) -> Option<usize>,
+    mut heuristic: impl FnMut(Coord) -> usize
+) -> Option<Path> {
+    let mut search = Search::new(grid, start);
+    if !grid.in_bounds(start) {
+        return None;
+    }
+
+    let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+    while let Some(Reverse((_, current_cost, current))) = queue.pop() {
+        if current == goal {
+            return search.path_to(goal);
+        }
+        if search.distance(current).is_some_and(|known| known < current_cost) {
+            continue;
+        }
+        for (_, neighbor, cell) in grid.neighbors4(current) {
+            let Some(step) = cost(&grid[current], cell) else {
+                continue;
+            };
+            let next_cost = current_cost + step;
+            if search.relax(current, neighbor, next_cost) {
+                queue.push(Reverse((next_cost + heuristic(neighbor), next_cost, neighbor)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(_: &char, to: &char) -> bool {
+        *to != '#'
+    }
+
+    // Walls can't be entered, `x` costs 5 to step onto, anything else 1
+    fn cost(_: &char, to: &char) -> Option<usize> {
+        match to {
+            '#' => None,
+            'x' => Some(5),
+            _ => Some(1),
+        }
+    }
+
+    fn manhattan(goal: Coord) -> impl FnMut(Coord) -> usize {
+        move |cell| cell.manhattan(goal) as usize
+    }
+
+    #[test]
+    fn bfs_distances_cover_the_maze() {
+        let grid: Grid<char> = "...\n.#.\n...".parse().unwrap();
+        let search = bfs(&grid, Coord::new(0, 0), open);
+        let expected: Grid<Option<usize>> = Grid::from_rows(vec![
+            vec![Some(0), Some(1), Some(2)],
+            vec![Some(1), None, Some(3)],
+            vec![Some(2), Some(3), Some(4)],
+        ]).unwrap();
+        assert_eq!(search.distances, expected);
+        assert_eq!(search.distance(Coord::new(2, 2)), Some(4));
+        assert_eq!(search.distance(Coord::new(1, 1)), None);
+    }
+
+    #[test]
+    fn bfs_finds_every_shortest_path() {
+        let grid: Grid<char> = "...\n.#.\n...".parse().unwrap();
+        let search = bfs(&grid, Coord::new(0, 0), open);
+
+        let path = search.path_to(Coord::new(2, 2)).unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.cells.len(), 5);
+        assert_eq!((path.cells[0], path.cells[4]), (Coord::new(0, 0), Coord::new(2, 2)));
+
+        let mut paths: Vec<Vec<Coord>> = search.all_paths_to(Coord::new(2, 2)).into_iter().map(|path| path.cells).collect();
+        paths.sort();
+        assert_eq!(paths, vec![
+            vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0), Coord::new(2, 1), Coord::new(2, 2)],
+            vec![Coord::new(0, 0), Coord::new(0, 1), Coord::new(0, 2), Coord::new(1, 2), Coord::new(2, 2)],
+        ]);
+        assert_eq!(search.on_shortest_paths(Coord::new(2, 2)).len(), 8);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_go_around_expensive_cells() {
+        let grid: Grid<char> = "...\nx#.\n...".parse().unwrap();
+        let search = dijkstra(&grid, Coord::new(0, 0), cost);
+        assert_eq!(search.distance(Coord::new(0, 1)), Some(5));
+        assert_eq!(search.distance(Coord::new(0, 2)), Some(6));
+        assert_eq!(search.distance(Coord::new(2, 2)), Some(4));
+
+        let expected = Path { cost: 4, cells: vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0), Coord::new(2, 1), Coord::new(2, 2)] };
+        assert_eq!(search.path_to(Coord::new(2, 2)), Some(expected.clone()));
+        assert_eq!(search.all_paths_to(Coord::new(2, 2)), vec![expected.clone()]);
+        assert_eq!(astar(&grid, Coord::new(0, 0), Coord::new(2, 2), cost, manhattan(Coord::new(2, 2))), Some(expected));
+    }
+
+    #[test]
+    fn unreachable_goals_have_no_path() {
+        let grid: Grid<char> = "..#\n.##\n#..".parse().unwrap();
+        let search = bfs(&grid, Coord::new(0, 0), open);
+        assert_eq!(search.distance(Coord::new(2, 2)), None);
+        assert_eq!(search.path_to(Coord::new(2, 2)), None);
+        assert!(search.all_paths_to(Coord::new(2, 2)).is_empty());
+        assert!(search.on_shortest_paths(Coord::new(2, 2)).is_empty());
+        assert_eq!(astar(&grid, Coord::new(0, 0), Coord::new(2, 2), cost, manhattan(Coord::new(2, 2))), None);
+        assert_eq!(astar(&grid, Coord::new(-1, 0), Coord::new(0, 0), cost, manhattan(Coord::new(0, 0))), None);
+    }
+}
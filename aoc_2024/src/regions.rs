@@ -0,0 +1,142 @@
+//! Splitting a grid into connected regions, e.g. the garden plots of a farm map.
+//! Regions are found with an iterative flood fill, so large regions can't overflow the stack.
+use crate::utils::{ Coord, Direction, Grid };
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    /// Every cell in the region, in the order the flood fill reached them.
+    pub cells: Vec<Coord>,
+    /// Number of cell edges that border another region or the edge of the grid.
+    pub perimeter: usize,
+    /// Number of straight fence runs around the region, holes included.
+    pub sides: usize,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Every connected region of the grid, in reading order of their first cell,
+    /// along with a grid holding each cell's index into them.
+    /// Orthogonal neighbors belong to the same region when `same_region(cell, neighbor)` says so.
+    pub fn regions(&self, same_region: impl Fn(&T, &T) -> bool) -> (Vec<Region>, Grid<usize>) {
+        let mut labels: Grid<Option<usize>> = self.map(|_| None);
+        let mut regions = Vec::new();
+
+        for (seed, _) in self.iter() {
+            if labels[seed].is_some() {
+                continue;
+            }
+            let label = regions.len();
+            labels.set(seed, Some(label));
+            let mut cells = Vec::new();
+            let mut pending = vec![seed];
+            while let Some(current) = pending.pop() {
+                cells.push(current);
+                for (_, neighbor, cell) in self.neighbors4(current) {
+                    if labels[neighbor].is_none() && same_region(&self[current], cell) {
+                        labels.set(neighbor, Some(label));
+                        pending.push(neighbor);
+                    }
+                }
+            }
+
+            let inside = |position: Coord| labels.get(position) == Some(&Some(label));
+            let perimeter = cells
+                .iter()
+                .map(|cell| Direction::orthogonal().filter(|direction| !inside(direction.jump_cell(*cell))).count())
+                .sum();
+            // A polygon has as many sides as corners. Each cell checks its four corners:
+            // outside corners have both orthogonal neighbors outside the region,
+            // inside corners have both inside but the diagonal between them outside.
+            let sides = cells
+                .iter()
+                .map(|cell| {
+                    Direction::orthogonal()
+                        .filter(|direction| {
+                            let turned = direction.turn_right();
+                            let ahead = inside(direction.jump_cell(*cell));
+                            let beside = inside(turned.jump_cell(*cell));
+                            let diagonal = inside(*cell + direction.delta() + turned.delta());
+                            (!ahead && !beside) || (ahead && beside && !diagonal)
+                        })
+                        .count()
+                })
+                .sum();
+
+            regions.push(Region { cells, perimeter, sides });
+        }
+        (regions, labels.map(|label| label.expect("every cell was flood filled")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plots(map: &str) -> (Vec<Region>, Grid<usize>) {
+        let grid: Grid<char> = map.parse().unwrap();
+        grid.regions(|plot, neighbor| plot == neighbor)
+    }
+
+    fn price(regions: &[Region]) -> usize {
+        regions.iter().map(|region| region.area() * region.perimeter).sum()
+    }
+
+    fn bulk_price(regions: &[Region]) -> usize {
+        regions.iter().map(|region| region.area() * region.sides).sum()
+    }
+
+    #[test]
+    fn small_example() {
+        let (regions, labels) = plots("AAAA\nBBCD\nBBCC\nEEEC");
+        let measures: Vec<(usize, usize, usize)> = regions
+            .iter()
+            .map(|region| (region.area(), region.perimeter, region.sides))
+            .collect();
+        // A, B, C, D and E, in reading order of their first cell
+        assert_eq!(measures, vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]);
+        assert_eq!(price(&regions), 140);
+        assert_eq!(bulk_price(&regions), 80);
+
+        let expected: Grid<usize> = "0000\n1123\n1122\n4442".parse::<Grid<char>>().unwrap().map(|label| label.to_digit(10).unwrap() as usize);
+        assert_eq!(labels, expected);
+    }
+
+    #[test]
+    fn regions_inside_regions() {
+        let (regions, labels) = plots("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO");
+        assert_eq!(regions.len(), 5);
+        assert_eq!((regions[0].area(), regions[0].perimeter, regions[0].sides), (21, 36, 20));
+        assert_eq!(price(&regions), 772);
+        assert_eq!(bulk_price(&regions), 436);
+        for (position, label) in labels.iter() {
+            assert!(regions[*label].cells.contains(&position));
+        }
+    }
+
+    #[test]
+    fn e_shaped_example() {
+        let (regions, _) = plots("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE");
+        assert_eq!(bulk_price(&regions), 236);
+    }
+
+    #[test]
+    fn diagonal_touches_are_separate_regions() {
+        let (regions, _) = plots("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        assert_eq!(regions.len(), 3);
+        assert_eq!(bulk_price(&regions), 368);
+    }
+
+    #[test]
+    fn larger_example() {
+        let map = "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\nVVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE";
+        let (regions, _) = plots(map);
+        assert_eq!(regions.len(), 11);
+        assert_eq!(price(&regions), 1930);
+        assert_eq!(bulk_price(&regions), 1206);
+    }
+}
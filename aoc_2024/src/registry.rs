@@ -0,0 +1,42 @@
+//! Maps (year, day) to the solution that solves it.
+//! Adding a day means adding one line to `REGISTRY`.
+use crate::solution::Solution;
+use crate::{ day8, day9, week1 };
+
+pub const YEAR: u16 = 2024;
+
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub build: fn() -> Box<dyn Solution>,
+}
+
+pub const REGISTRY: &[Entry] = &[
+    Entry { year: 2024, day: 1, build: || Box::new(week1::Day1::default()) },
+    Entry { year: 2024, day: 2, build: || Box::new(week1::Day2::default()) },
+    Entry { year: 2024, day: 3, build: || Box::new(week1::Day3::default()) },
+    Entry { year: 2024, day: 4, build: || Box::new(week1::Day4::default()) },
+    Entry { year: 2024, day: 5, build: || Box::new(week1::Day5::default()) },
+    Entry { year: 2024, day: 6, build: || Box::new(week1::Day6::default()) },
+    Entry { year: 2024, day: 7, build: || Box::new(week1::Day7::default()) },
+    Entry { year: 2024, day: 8, build: || Box::new(day8::Day8::default()) },
+    Entry { year: 2024, day: 9, build: || Box::new(day9::Day9::default()) },
+];
+
+/// Builds a fresh, unparsed solution for the given day.
+pub fn lookup(year: u16, day: u8) -> Option<Box<dyn Solution>> {
+    REGISTRY.iter()
+        .find(|entry| entry.year == year && entry.day == day)
+        .map(|entry| (entry.build)())
+}
+
+/// Every day with a registered solution for the given year, in order.
+pub fn days(year: u16) -> Vec<u8> {
+    let mut days: Vec<u8> = REGISTRY.iter()
+        .filter(|entry| entry.year == year)
+        .map(|entry| entry.day)
+        .collect();
+    days.sort();
+    days
+}
+
@@ -0,0 +1,307 @@
+//! Drawing grids as text, for debugging a day in the terminal or in CI logs.
+//! Color is opt-in per symbol and per layer, and is left out entirely when `NO_COLOR` is set or stdout
+//! isn't a terminal, so a dumped grid stays readable wherever it ends up. Large grids can be cropped
+//! to a viewport, with rulers to tell which rows and columns are on screen.
+use std::{ collections::HashMap, fmt };
+
+pub use colored::Color;
+
+use crate::layers::Layers;
+use crate::utils::{ Coord, Grid };
+
+/// Foreground and background colors for a cell. Either can be left as the terminal's own.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Style {
+    pub fn fg(color: Color) -> Style {
+        Style { fg: Some(color), bg: None }
+    }
+
+    pub fn bg(color: Color) -> Style {
+        Style { fg: None, bg: Some(color) }
+    }
+
+    /// The same style on a `color` background.
+    pub fn on(self, color: Color) -> Style {
+        Style { bg: Some(color), ..self }
+    }
+
+    /// Writes `text` in this style. Nothing but the text is written when there is no color to set.
+    fn paint(&self, f: &mut impl fmt::Write, text: &str) -> fmt::Result {
+        let codes: Vec<String> = [self.fg.map(|color| color.to_fg_str()), self.bg.map(|color| color.to_bg_str())]
+            .into_iter()
+            .flatten()
+            .map(String::from)
+            .collect();
+        if codes.is_empty() {
+            write!(f, "{}", text)
+        } else {
+            write!(f, "\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+        }
+    }
+}
+
+/// Settings for drawing grids, built up by chaining its methods onto `Renderer::new()`
+/// and then used through `render` or `display`.
+#[derive(Clone, Debug)]
+pub struct Renderer<'a, P = char> {
+    layers: Option<&'a Layers<P>>,
+    /// Layers to draw over the grid, with their styles. Where several annotate a cell, the first wins.
+    layer_styles: Vec<(String, Style)>,
+    symbol_styles: HashMap<char, Style>,
+    color: bool,
+    rulers: bool,
+    /// Top left corner, width and height of the part of the grid to draw.
+    viewport: Option<(Coord, usize, usize)>,
+}
+
+impl Renderer<'_> {
+    /// Plain cells, no layers, no rulers, the whole grid.
+    /// Color is on only if `colored` would use it: stdout is a terminal and neither `NO_COLOR`
+    /// nor `CLICOLOR=0` is set, unless `CLICOLOR_FORCE` says otherwise.
+    pub fn new() -> Renderer<'static> {
+        Renderer {
+            layers: None,
+            layer_styles: Vec::new(),
+            symbol_styles: HashMap::new(),
+            color: colored::control::SHOULD_COLORIZE.should_colorize(),
+            rulers: false,
+            viewport: None,
+        }
+    }
+}
+
+impl Default for Renderer<'_> {
+    fn default() -> Self {
+        Renderer::new()
+    }
+}
+
+impl<'a, P> Renderer<'a, P> {
+    /// Draws cells showing `symbol` in `style`.
+    pub fn symbol(mut self, symbol: char, style: Style) -> Renderer<'a, P> {
+        self.symbol_styles.insert(symbol, style);
+        self
+    }
+
+    /// Where the layers named by `layer` come from.
+    pub fn layers<'b, Q>(self, layers: &'b Layers<Q>) -> Renderer<'b, Q> {
+        Renderer {
+            layers: Some(layers),
+            layer_styles: self.layer_styles,
+            symbol_styles: self.symbol_styles,
+            color: self.color,
+            rulers: self.rulers,
+            viewport: self.viewport,
+        }
+    }
+
+    /// Draws layer `name` over the grid in `style`, using the glyphs described on `layers::Overlay`.
+    /// Layers added earlier win where several annotate the same cell.
+    pub fn layer(mut self, name: &str, style: Style) -> Renderer<'a, P> {
+        self.layer_styles.push((String::from(name), style));
+        self
+    }
+
+    /// Forces color on or off, whatever the environment says.
+    pub fn color(mut self, color: bool) -> Renderer<'a, P> {
+        self.color = color;
+        self
+    }
+
+    /// Numbers the rows down the left and the columns across the top.
+    pub fn rulers(mut self, rulers: bool) -> Renderer<'a, P> {
+        self.rulers = rulers;
+        self
+    }
+
+    /// Only draws the `width` by `height` rectangle with its top left corner at `origin`.
+    /// Whatever part of it lies off the grid is left out.
+    pub fn viewport(mut self, origin: Coord, width: usize, height: usize) -> Renderer<'a, P> {
+        self.viewport = Some((origin, width, height));
+        self
+    }
+
+    /// The columns and rows to draw, as half-open ranges clipped to the grid.
+    pub(crate) fn visible<T>(&self, grid: &Grid<T>) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let Some((origin, width, height)) = self.viewport else {
+            return (0..grid.width(), 0..grid.height());
+        };
+        let clip = |start: i32, length: usize, limit: usize| {
+            let end = (i64::from(start) + length as i64).clamp(0, limit as i64) as usize;
+            let start = start.clamp(0, limit as i32) as usize;
+            start..end.max(start)
+        };
+        let (columns, rows) = (clip(origin.x, width, grid.width()), clip(origin.y, height, grid.height()));
+        // A viewport beside or below the grid shows nothing at all, not a column of empty rows
+        if columns.is_empty() || rows.is_empty() {
+            return (0..0, 0..0);
+        }
+        (columns, rows)
+    }
+}
+
+impl<P: fmt::Display> Renderer<'_, P> {
+    /// What to draw at `position` and in which style: the first chosen layer with something there,
+    /// otherwise the cell itself.
+    pub(crate) fn cell<T: fmt::Display>(&self, grid: &Grid<T>, position: Coord) -> (String, Style) {
+        let annotation = self.layers.and_then(|layers| {
+            self.layer_styles
+                .iter()
+                .find_map(|(name, style)| layers.get(name)?.glyph(position).map(|glyph| (glyph, *style)))
+        });
+        annotation.unwrap_or_else(|| {
+            let text = grid.get(position).map_or_else(String::new, |cell| cell.to_string());
+            let mut symbols = text.chars();
+            let style = match (symbols.next(), symbols.next()) {
+                (Some(symbol), None) => self.symbol_styles.get(&symbol).copied().unwrap_or_default(),
+                _ => Style::default(),
+            };
+            (text, style)
+        })
+    }
+
+    /// Draws `grid` into `f`, passing on any error from writing.
+    pub fn render<T: fmt::Display>(&self, grid: &Grid<T>, f: &mut impl fmt::Write) -> fmt::Result {
+        let (columns, rows) = self.visible(grid);
+        let ruler_style = if self.color { Style::fg(Color::BrightBlack) } else { Style::default() };
+        let gutter = rows.end.saturating_sub(1).to_string().len();
+
+        if self.rulers {
+            // One line per digit of the column numbers, most significant first.
+            // Leading zeros are left blank so the numbers read top to bottom.
+            let digits = columns.end.saturating_sub(1).to_string().len();
+            for place in (0..digits as u32).rev() {
+                let scale = 10usize.pow(place);
+                let line: String = columns
+                    .clone()
+                    .map(|x| match x {
+                        x if place > 0 && x < scale => ' ',
+                        x => char::from(b'0' + (x / scale % 10) as u8),
+                    })
+                    .collect();
+                write!(f, "{} ", " ".repeat(gutter))?;
+                ruler_style.paint(f, &line)?;
+                writeln!(f)?;
+            }
+        }
+
+        for y in rows {
+            if self.rulers {
+                ruler_style.paint(f, &format!("{:>width$} ", y, width = gutter))?;
+            }
+            for x in columns.clone() {
+                let (text, style) = self.cell(grid, Coord::from_index(x, y));
+                if self.color {
+                    style.paint(f, &text)?;
+                } else {
+                    write!(f, "{}", text)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+
+    /// `grid` drawn with these settings, for use with `{}`.
+    pub fn display<'r, T>(&'r self, grid: &'r Grid<T>) -> Rendered<'r, T, P> {
+        Rendered { renderer: self, grid }
+    }
+}
+
+/// A grid paired with a renderer, see `Renderer::display`.
+pub struct Rendered<'r, T, P> {
+    renderer: &'r Renderer<'r, P>,
+    grid: &'r Grid<T>,
+}
+
+impl<T: fmt::Display, P: fmt::Display> fmt::Display for Rendered<'_, T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.renderer.render(self.grid, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain() -> Renderer<'static> {
+        Renderer::new().color(false)
+    }
+
+    fn draw<T: fmt::Display, P: fmt::Display>(renderer: &Renderer<P>, grid: &Grid<T>) -> String {
+        let mut out = String::new();
+        renderer.render(grid, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn rulers_number_rows_and_columns() {
+        let grid: Grid<char> = "abcdefghijkl\n............\nABCDEFGHIJKL".parse().unwrap();
+        let expected = concat!(
+            "            11\n",
+            "  012345678901\n",
+            "0 abcdefghijkl\n",
+            "1 ............\n",
+            "2 ABCDEFGHIJKL\n",
+        );
+        assert_eq!(draw(&plain().rulers(true), &grid), expected);
+        assert_eq!(draw(&plain(), &grid), grid.to_string());
+    }
+
+    #[test]
+    fn viewport_crops_to_the_grid() {
+        let grid: Grid<char> = "abcd\nefgh\nijkl".parse().unwrap();
+        assert_eq!(draw(&plain().viewport(Coord::new(1, 1), 2, 2), &grid), "fg\njk\n");
+        // Whatever hangs off the grid is left out
+        assert_eq!(draw(&plain().viewport(Coord::new(-1, 1), 3, 5), &grid), "ef\nij\n");
+        assert_eq!(draw(&plain().viewport(Coord::new(9, 0), 2, 2), &grid), "");
+        assert_eq!(draw(&plain().viewport(Coord::new(2, 1), 2, 2).rulers(true), &grid), "  23\n1 gh\n2 kl\n");
+    }
+
+    #[test]
+    fn layers_are_drawn_without_color() {
+        let grid: Grid<char> = "#..\n...".parse().unwrap();
+        let mut layers: Layers = Layers::for_grid(&grid);
+        layers.mark("seen", Coord::new(1, 1));
+        let renderer = plain()
+            .symbol('#', Style::fg(Color::Red))
+            .layers(&layers)
+            .layer("seen", Style::bg(Color::Yellow));
+        assert_eq!(draw(&renderer, &grid), "#..\n.X.\n");
+        assert_eq!(renderer.display(&grid).to_string(), "#..\n.X.\n");
+    }
+
+    #[test]
+    fn color_wraps_styled_cells_in_escape_codes() {
+        let grid: Grid<char> = "#.".parse().unwrap();
+        let renderer = Renderer::new().color(true).symbol('#', Style::fg(Color::Red).on(Color::Blue));
+        assert_eq!(draw(&renderer, &grid), "\x1b[31;44m#\x1b[0m.\n");
+    }
+
+    /// Accepts `room` characters, then refuses everything.
+    struct Full {
+        room: usize,
+    }
+
+    impl fmt::Write for Full {
+        fn write_str(&mut self, text: &str) -> fmt::Result {
+            self.room = self.room.checked_sub(text.chars().count()).ok_or(fmt::Error)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_errors_are_passed_on() {
+        let grid: Grid<char> = "ab\ncd".parse().unwrap();
+        for room in 0..6 {
+            assert_eq!(plain().render(&grid, &mut Full { room }), Err(fmt::Error), "room for {}", room);
+            assert_eq!(plain().rulers(true).render(&grid, &mut Full { room }), Err(fmt::Error));
+        }
+        assert_eq!(plain().render(&grid, &mut Full { room: 6 }), Ok(()));
+    }
+}
@@ -0,0 +1,212 @@
+//! Generating the boilerplate for a new day, so release morning starts at the puzzle instead of the plumbing.
+//! `new --day N` writes `src/dayN.rs` from a template, declares the module in `lib.rs`, registers it,
+//! and creates the input, example and answers files for it.
+use std::{ fs, path::{ Path, PathBuf } };
+
+use aoc_2024::registry;
+
+use crate::input;
+
+const TEMPLATE: &str = r#"use crate::error::{ AocError, AocResult };
+use crate::solution::{ Answer, Solution };
+
+#[derive(Default)]
+pub struct Day{N} {
+    // Whatever parse makes of the input goes here
+}
+
+impl Solution for Day{N} {
+    fn parse(&mut self, _input: &str) -> AocResult<()> {
+        Ok(())
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[include_str!("../input/day{N}_example1.txt")]
+    }
+
+    fn part1(&self) -> AocResult<Answer> {
+        Err(AocError::new("not solved yet"))
+    }
+
+    fn part2(&self) -> AocResult<Answer> {
+        Err(AocError::new("not solved yet"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::answers::{ AnswerStore, Verdict };
+    use crate::solution::Part;
+
+    // Each example is checked against input/day{N}_exampleK.answers.
+    // Parts with no answer there yet, or that aren't solved yet, are skipped.
+    #[test]
+    fn examples_match_their_answers() {
+        for (index, example) in Day{N}::default().examples().iter().enumerate() {
+            let answers_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("input/day{N}_example{}.answers", index + 1));
+            let answers = AnswerStore::load(&answers_path).unwrap();
+            let mut day = Day{N}::default();
+            day.parse(example).unwrap();
+            for part in Part::BOTH {
+                let answer = match day.solve(part) {
+                    Ok(answer) => answer,
+                    Err(err) if err.message == "not solved yet" => continue,
+                    Err(err) => panic!("example {} part {}: {}", index + 1, part, err),
+                };
+                if let Verdict::Fail(reason) = answers.check(part, &answer) {
+                    panic!("example {} part {}: got {}, {}", index + 1, part, answer, reason);
+                }
+            }
+        }
+    }
+}
+"#;
+
+const ANSWERS_TEMPLATE: &str = "\
+# Answers given in the puzzle text for this example, e.g.
+# part1 correct 42
+";
+
+fn src_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("can't read {}: {}", path.display(), err))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|err| format!("can't write {}: {}", path.display(), err))
+}
+
+/// Creates `path` with `contents` unless something is already there. Returns whether it was created.
+fn create(path: &Path, contents: &str) -> Result<bool, String> {
+    if path.exists() {
+        return Ok(false);
+    }
+    write(path, contents).map(|()| true)
+}
+
+/// Adds `pub mod dayN;` after the last declared day module.
+fn declare_module(lib: &str, day: u8) -> Result<String, String> {
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let last_day = lines
+        .iter()
+        .rposition(|line| line.starts_with("pub mod day"))
+        .ok_or("can't find where the day modules are declared")?;
+    let declaration = format!("pub mod day{};", day);
+    lines.insert(last_day + 1, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Imports the module into the registry and adds its entry at the end of `REGISTRY`.
+fn register(registry: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{}", day);
+    let mut output = Vec::new();
+    let mut in_registry = false;
+    let mut imported = false;
+    let mut entered = false;
+
+    for line in registry.lines() {
+        if let Some(modules) = line.strip_prefix("use crate::{ ").and_then(|rest| rest.strip_suffix(" };")) {
+            let mut modules: Vec<&str> = modules.split(", ").collect();
+            modules.push(&module);
+            // dayN modules in numeric order, anything else (week1) after them
+            modules.sort_by_key(|name| (name.strip_prefix("day").and_then(|n| n.parse::<u8>().ok()).unwrap_or(u8::MAX), *name));
+            output.push(format!("use crate::{{ {} }};", modules.join(", ")));
+            imported = true;
+            continue;
+        }
+        if line.starts_with("pub const REGISTRY") {
+            in_registry = true;
+        } else if in_registry && line == "];" {
+            output.push(format!(
+                "    Entry {{ year: {}, day: {}, build: || Box::new({}::Day{}::default()) }},",
+                registry::YEAR, day, module, day
+            ));
+            in_registry = false;
+            entered = true;
+        }
+        output.push(String::from(line));
+    }
+
+    match (imported, entered) {
+        (true, true) => Ok(output.join("\n") + "\n"),
+        _ => Err(String::from("can't find the module imports or REGISTRY in registry.rs")),
+    }
+}
+
+/// Generates and wires up everything a new day needs. Refuses to touch a day that already exists.
+pub fn new_day(day: u8) -> Result<Vec<PathBuf>, String> {
+    let module_path = src_dir().join(format!("day{}.rs", day));
+    if registry::lookup(registry::YEAR, day).is_some() || module_path.exists() {
+        return Err(format!("day {} already exists", day));
+    }
+
+    let lib_path = src_dir().join("lib.rs");
+    let registry_path = src_dir().join("registry.rs");
+    // Work out every edit before writing anything, so a failure leaves the tree as it was
+    let lib = declare_module(&read(&lib_path)?, day)?;
+    let registry = register(&read(&registry_path)?, day)?;
+
+    // Examples stay in this crate even with AOC_INPUT_DIR set, since the day compiles them in
+    let example_path = input::example_path(day, 1);
+    for dir in [input::input_dir(), example_path.with_file_name("")] {
+        fs::create_dir_all(&dir).map_err(|err| format!("can't create {}: {}", dir.display(), err))?;
+    }
+    let mut touched = vec![module_path.clone(), lib_path.clone(), registry_path.clone()];
+    write(&module_path, &TEMPLATE.replace("{N}", &day.to_string()))?;
+    write(&lib_path, &lib)?;
+    write(&registry_path, &registry)?;
+    for (path, contents) in [
+        (input::default_input_path(day), ""),
+        (example_path.clone(), ""),
+        (example_path.with_extension("answers"), ANSWERS_TEMPLATE),
+    ] {
+        if create(&path, contents)? {
+            touched.push(path);
+        }
+    }
+    Ok(touched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declares_the_module_after_the_last_day() {
+        let lib = "pub mod solution;\npub mod week1;\npub mod day8;\npub mod day9;\n";
+        assert_eq!(
+            declare_module(lib, 10),
+            Ok(String::from("pub mod solution;\npub mod week1;\npub mod day8;\npub mod day9;\npub mod day10;\n"))
+        );
+        assert!(declare_module("pub mod solution;\n", 10).is_err());
+    }
+
+    #[test]
+    fn registers_the_day_in_order() {
+        let registry = "\
+use crate::{ day8, day9, week1 };
+
+pub const REGISTRY: &[Entry] = &[
+    Entry { year: 2024, day: 9, build: || Box::new(day9::Day9::default()) },
+];
+";
+        assert_eq!(
+            register(registry, 10),
+            Ok(String::from("\
+use crate::{ day8, day9, day10, week1 };
+
+pub const REGISTRY: &[Entry] = &[
+    Entry { year: 2024, day: 9, build: || Box::new(day9::Day9::default()) },
+    Entry { year: 2024, day: 10, build: || Box::new(day10::Day10::default()) },
+];
+"))
+        );
+        assert!(register("pub const REGISTRY: &[Entry] = &[\n];\n", 10).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! The shape every day's solution is expected to take.
+//! Runners, benchmarks and checks only ever talk to days through this trait,
+//! so nothing outside a day module has to know how that day stores its input.
+use core::fmt;
+use std::{ convert::Infallible, str::FromStr };
+
+use crate::error::AocResult;
+
+/// Which half of a day's puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    /// Parses the `1`/`2` used on the command line and on the website.
+    pub fn from_number(raw: &str) -> Option<Part> {
+        match raw {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A puzzle answer.
+/// Nearly every answer is a number, but a handful of puzzles every year want a string back.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Num(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Num(num) => write!(f, "{}", num),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Anything that reads as an integer becomes `Num`, everything else is kept as text.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(raw: &str) -> Result<Answer, Infallible> {
+        let raw = raw.trim();
+        Ok(match raw.parse::<i64>() {
+            Ok(num) => Answer::Num(num),
+            Err(_) => Answer::Text(String::from(raw)),
+        })
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Answer {
+        Answer::Num(value as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        Answer::Num(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        Answer::Num(value as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        Answer::Text(String::from(value))
+    }
+}
+
+/// One day of puzzles.
+/// `parse` is called exactly once with the full puzzle input before either part is solved,
+/// and each part then works off whatever `parse` stored on `self`.
+/// Malformed input is reported through the returned error rather than a panic.
+pub trait Solution {
+    fn parse(&mut self, input: &str) -> AocResult<()>;
+    /// The worked examples from the puzzle text, usually embedded with `include_str!`.
+    fn examples(&self) -> &'static [&'static str] {
+        &[]
+    }
+    fn part1(&self) -> AocResult<Answer>;
+    fn part2(&self) -> AocResult<Answer>;
+
+    fn solve(&self, part: Part) -> AocResult<Answer> {
+        match part {
+            Part::One => self.part1(),
+            Part::Two => self.part2(),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! A grid that only stores the cells that hold something, keyed by `Coord`.
+//! For puzzles whose grid grows without bound, goes negative, or is mostly empty over a huge range,
+//! where a dense `Grid` would be wasteful or impossible. Queries mirror `Grid`'s, except that
+//! `None` means "nothing stored there" rather than "off the grid".
+use std::{ collections::HashMap, fmt, slice::Iter };
+
+use crate::utils::{ Coord, Direction, Grid };
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Coord, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new() }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// The cells of `grid` that pass `keep`, at the same positions.
+    pub fn from_dense(grid: &Grid<T>, mut keep: impl FnMut(&T) -> bool) -> SparseGrid<T>
+    where
+        T: Clone,
+    {
+        grid.iter()
+            .filter(|(_, cell)| keep(cell))
+            .map(|(position, cell)| (position, cell.clone()))
+            .collect()
+    }
+
+    /// Number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: Coord) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    pub fn get(&self, position: Coord) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn get_mut(&mut self, position: Coord) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+
+    /// Stores `value` at `position`, handing back what was there.
+    pub fn set(&mut self, position: Coord, value: T) -> Option<T> {
+        self.cells.insert(position, value)
+    }
+
+    /// Empties the cell at `position`, handing back what was there.
+    pub fn remove(&mut self, position: Coord) -> Option<T> {
+        self.cells.remove(&position)
+    }
+
+    /// The occupied cells one step from `position` in each of `directions`.
+    fn neighbors_in(&self, position: Coord, directions: Iter<'static, Direction>) -> impl Iterator<Item = (Direction, Coord, &T)> {
+        directions.filter_map(move |direction| {
+            let neighbor = direction.jump_cell(position);
+            self.get(neighbor).map(|cell| (*direction, neighbor, cell))
+        })
+    }
+
+    /// The occupied orthogonal neighbors of `position`.
+    pub fn neighbors4(&self, position: Coord) -> impl Iterator<Item = (Direction, Coord, &T)> {
+        self.neighbors_in(position, Direction::orthogonal())
+    }
+
+    /// The occupied neighbors of `position`, diagonals included.
+    pub fn neighbors8(&self, position: Coord) -> impl Iterator<Item = (Direction, Coord, &T)> {
+        self.neighbors_in(position, Direction::iterator())
+    }
+
+    /// The occupied orthogonal neighbors of `position` whose cell passes `predicate`.
+    pub fn neighbors_where(&self, position: Coord, mut predicate: impl FnMut(&T) -> bool) -> impl Iterator<Item = (Direction, Coord, &T)> {
+        self.neighbors4(position).filter(move |(_, _, cell)| predicate(cell))
+    }
+
+    /// Every occupied cell with its position, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells.iter().map(|(position, cell)| (*position, cell))
+    }
+
+    /// Every occupied cell with its position in reading order, like `Grid::iter`.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (Coord, &T)> {
+        let mut cells: Vec<(Coord, &T)> = self.iter().collect();
+        cells.sort_unstable_by_key(|(position, _)| *position);
+        cells.into_iter()
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle holding every occupied cell,
+    /// or `None` if nothing is stored.
+    pub fn bounds(&self) -> Option<(Coord, Coord)> {
+        let mut positions = self.cells.keys();
+        let first = *positions.next()?;
+        Some(positions.fold((first, first), |(min, max), position| {
+            (
+                Coord { x: min.x.min(position.x), y: min.y.min(position.y) },
+                Coord { x: max.x.max(position.x), y: max.y.max(position.y) },
+            )
+        }))
+    }
+
+    /// A dense grid covering `bounds()`, with empty cells set to `fill`,
+    /// along with where the dense grid's (0, 0) sits in this grid's coordinates.
+    pub fn to_dense(&self, fill: T) -> (Grid<T>, Coord)
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds() else {
+            return (Grid::new(0, 0, fill), Coord::default());
+        };
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let grid = Grid::from_fn(width, height, |position| self.get(min + position).unwrap_or(&fill).clone());
+        (grid, min)
+    }
+}
+
+impl<T> FromIterator<(Coord, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coord, T)>>(cells: I) -> SparseGrid<T> {
+        SparseGrid { cells: cells.into_iter().collect() }
+    }
+}
+
+/// Every cell stored, at the same position.
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> SparseGrid<T> {
+        grid.into_cells().collect()
+    }
+}
+
+/// The rectangle given by `bounds()`, one row per line, with `.` for empty cells.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.get(Coord { x, y }) {
+                    Some(cell) => write!(f, "{}", cell)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scattered() -> SparseGrid<char> {
+        [(Coord::new(-2, -1), 'a'), (Coord::new(1, 0), 'b'), (Coord::new(0, 2), 'c')].into_iter().collect()
+    }
+
+    #[test]
+    fn bounds_hold_every_cell() {
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+        assert_eq!(SparseGrid::from_iter([(Coord::new(3, -4), 'x')]).bounds(), Some((Coord::new(3, -4), Coord::new(3, -4))));
+        assert_eq!(scattered().bounds(), Some((Coord::new(-2, -1), Coord::new(1, 2))));
+    }
+
+    #[test]
+    fn to_dense_shifts_negative_coordinates() {
+        let (grid, origin) = scattered().to_dense('.');
+        assert_eq!(origin, Coord::new(-2, -1));
+        assert_eq!((grid.width(), grid.height()), (4, 4));
+        assert_eq!(grid.to_string(), "a...\n...b\n....\n..c.\n");
+        // Positions in the dense grid are offset by the origin
+        assert_eq!(grid[Coord::new(1, 0) - origin], 'b');
+
+        let (empty, origin) = SparseGrid::new().to_dense('.');
+        assert_eq!((empty.width(), empty.height(), origin), (0, 0, Coord::default()));
+    }
+
+    #[test]
+    fn display_fills_the_gaps() {
+        assert_eq!(scattered().to_string(), "a...\n...b\n....\n..c.\n");
+        assert_eq!(SparseGrid::<char>::new().to_string(), "");
+    }
+
+    #[test]
+    fn round_trips_through_a_dense_grid() {
+        let grid: Grid<char> = "#.\n.#".parse().unwrap();
+        let walls = SparseGrid::from_dense(&grid, |cell| *cell == '#');
+        assert_eq!(walls.iter_sorted().collect::<Vec<_>>(), vec![(Coord::new(0, 0), &'#'), (Coord::new(1, 1), &'#')]);
+        assert_eq!(walls.to_dense('.'), (grid.clone(), Coord::default()));
+
+        let everything = SparseGrid::from(grid);
+        assert_eq!(everything.len(), 4);
+        assert_eq!(everything.get(Coord::new(1, 0)), Some(&'.'));
+    }
+
+    #[test]
+    fn grids_of_uncloneable_cells_convert() {
+        struct Token(usize);
+
+        let grid = Grid::from_fn(2, 2, |position| Token(position.y as usize * 2 + position.x as usize));
+        let sparse = SparseGrid::from(grid);
+        assert_eq!(sparse.get(Coord::new(1, 1)).map(|token| token.0), Some(3));
+        assert_eq!(sparse.get(Coord::new(0, 1)).map(|token| token.0), Some(2));
+    }
+}
@@ -0,0 +1,10 @@
+//! Helpers shared by the test modules.
+use std::{ env, fs, path::PathBuf, process };
+
+/// An empty directory of its own for each test, named so parallel test runs don't collide.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc_2024_{}_{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
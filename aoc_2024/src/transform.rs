@@ -0,0 +1,207 @@
+//! Reorienting grids and looking at parts of them.
+//! Reoriented grids keep the original's edge mode; extracted ones are always bounded.
+//! Many searches become "read every row of every orientation", and tilting puzzles become
+//! "tilt north" after rotating the grid so the wanted direction is north.
+use std::ops::Index;
+
+use crate::utils::{ Coord, Grid };
+
+impl<T: Clone> Grid<T> {
+    /// The grid a quarter turn clockwise: the left column becomes the top row.
+    pub fn rotate_cw(&self) -> Grid<T> {
+        let bottom = self.height() as i32 - 1;
+        Grid::from_fn(self.height(), self.width(), |position| self[Coord { x: position.y, y: bottom - position.x }].clone()).with_edges(self.edges())
+    }
+
+    /// The grid a quarter turn counter-clockwise: the right column becomes the top row.
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        let right = self.width() as i32 - 1;
+        Grid::from_fn(self.height(), self.width(), |position| self[Coord { x: right - position.y, y: position.x }].clone()).with_edges(self.edges())
+    }
+
+    /// Rows become columns, mirroring the grid along its main diagonal.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.height(), self.width(), |position| self[Coord { x: position.y, y: position.x }].clone()).with_edges(self.edges())
+    }
+
+    /// Mirrored left to right.
+    pub fn flip_h(&self) -> Grid<T> {
+        let right = self.width() as i32 - 1;
+        Grid::from_fn(self.width(), self.height(), |position| self[Coord { x: right - position.x, y: position.y }].clone()).with_edges(self.edges())
+    }
+
+    /// Mirrored top to bottom.
+    pub fn flip_v(&self) -> Grid<T> {
+        let bottom = self.height() as i32 - 1;
+        Grid::from_fn(self.width(), self.height(), |position| self[Coord { x: position.x, y: bottom - position.y }].clone()).with_edges(self.edges())
+    }
+
+    /// A copy of the `width` by `height` rectangle whose top left corner is `origin`,
+    /// or `None` if it doesn't fit on the grid.
+    pub fn extract(&self, origin: Coord, width: usize, height: usize) -> Option<Grid<T>> {
+        self.view(origin, width, height).map(|view| view.to_grid())
+    }
+}
+
+impl<T> Grid<T> {
+    /// A borrowed `width` by `height` window onto the grid with its top left corner at `origin`,
+    /// or `None` if it doesn't fit on the grid.
+    pub fn view(&self, origin: Coord, width: usize, height: usize) -> Option<GridView<'_, T>> {
+        let fits = |start: i32, length: usize, limit: usize| {
+            usize::try_from(start).is_ok_and(|start| start + length <= limit)
+        };
+        (fits(origin.x, width, self.width()) && fits(origin.y, height, self.height()))
+            .then_some(GridView { grid: self, origin, width, height })
+    }
+
+    /// Every `width` by `height` window that fits on the grid, in reading order of their top left corner.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        let across = (self.width() + 1).saturating_sub(width);
+        let down = (self.height() + 1).saturating_sub(height);
+        (0..down)
+            .flat_map(move |y| (0..across).map(move |x| Coord::from_index(x, y)))
+            .filter_map(move |origin| self.view(origin, width, height))
+    }
+}
+
+/// A rectangular part of a grid, addressed as if it were a grid of its own with (0, 0) at its top left.
+#[derive(Clone, Copy, Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Coord,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Where the view's top left corner sits on the underlying grid.
+    pub fn origin(&self) -> Coord {
+        self.origin
+    }
+
+    pub fn in_bounds(&self, position: Coord) -> bool {
+        usize::try_from(position.x).is_ok_and(|x| x < self.width)
+            && usize::try_from(position.y).is_ok_and(|y| y < self.height)
+    }
+
+    /// The cell at `position` within the view. Cells of the grid outside the view can't be reached.
+    pub fn get(&self, position: Coord) -> Option<&'a T> {
+        if self.in_bounds(position) {
+            self.grid.get(self.origin + position)
+        } else {
+            None
+        }
+    }
+
+    /// Every cell in the view with its position within the view, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &'a T)> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Coord::from_index(x, y)))
+            .map(move |position| (position, &self.grid[self.origin + position]))
+    }
+
+    /// A copy of just the viewed cells.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |position| self[position].clone())
+    }
+}
+
+impl<T> Index<Coord> for GridView<'_, T> {
+    type Output = T;
+
+    /// Panics when `position` is outside the view.
+    fn index(&self, position: Coord) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the {}x{} view", position, self.width, self.height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Edges;
+
+    fn grid(rows: &str) -> Grid<char> {
+        rows.parse().unwrap()
+    }
+
+    #[test]
+    fn four_quarter_turns_are_no_turn() {
+        let original = grid("abc\ndef");
+        assert_eq!(original.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(original.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(original.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), original);
+        assert_eq!(original.rotate_ccw().rotate_ccw().rotate_ccw().rotate_ccw(), original);
+        assert_eq!(original.rotate_cw().rotate_ccw(), original);
+    }
+
+    #[test]
+    fn transposing_twice_is_no_change() {
+        let original = grid("abc\ndef");
+        assert_eq!(original.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(original.transpose().transpose(), original);
+    }
+
+    #[test]
+    fn flips_mirror_the_grid() {
+        let original = grid("abc\ndef");
+        assert_eq!(original.flip_h().to_string(), "cba\nfed\n");
+        assert_eq!(original.flip_v().to_string(), "def\nabc\n");
+        assert_eq!(original.flip_h().flip_h(), original);
+        assert_eq!(original.flip_v().flip_v(), original);
+        // Flipping both ways is half a turn
+        assert_eq!(original.flip_h().flip_v(), original.rotate_cw().rotate_cw());
+    }
+
+    #[test]
+    fn reorienting_keeps_the_edge_mode() {
+        let wrapping = grid("ab\ncd").with_edges(Edges::Wrapping);
+        assert_eq!(wrapping.rotate_cw().edges(), Edges::Wrapping);
+        assert_eq!(wrapping.transpose().edges(), Edges::Wrapping);
+        assert_eq!(wrapping.extract(Coord::new(0, 0), 1, 1).unwrap().edges(), Edges::Bounded);
+    }
+
+    #[test]
+    fn sub_grids_must_fit() {
+        let original = grid("abc\ndef\nghi");
+        assert_eq!(original.extract(Coord::new(1, 1), 2, 2).unwrap().to_string(), "ef\nhi\n");
+        assert_eq!(original.extract(Coord::new(0, 0), 3, 3), Some(original.clone()));
+        assert_eq!(original.extract(Coord::new(2, 0), 2, 1), None);
+        assert_eq!(original.extract(Coord::new(0, 1), 1, 3), None);
+        assert_eq!(original.extract(Coord::new(-1, 0), 1, 1), None);
+        assert!(original.view(Coord::new(3, 3), 0, 0).is_some());
+    }
+
+    #[test]
+    fn views_only_reach_their_own_cells() {
+        let original = grid("abc\ndef\nghi");
+        let view = original.view(Coord::new(1, 1), 2, 2).unwrap();
+        assert_eq!((view.width(), view.height(), view.origin()), (2, 2, Coord::new(1, 1)));
+        assert_eq!(view.get(Coord::new(0, 0)), Some(&'e'));
+        assert_eq!(view[Coord::new(1, 1)], 'i');
+        assert_eq!(view.get(Coord::new(2, 0)), None);
+        assert_eq!(view.get(Coord::new(-1, 0)), None);
+        let cells: String = view.iter().map(|(_, cell)| cell).collect();
+        assert_eq!(cells, "efhi");
+    }
+
+    #[test]
+    fn windows_cover_every_fitting_position() {
+        let original = grid("abc\ndef\nghi");
+        let origins: Vec<Coord> = original.windows(2, 2).map(|view| view.origin()).collect();
+        assert_eq!(origins, vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(0, 1), Coord::new(1, 1)]);
+        assert_eq!(original.windows(3, 1).count(), 3);
+        assert_eq!(original.windows(4, 1).count(), 0);
+    }
+}
@@ -1,25 +1,50 @@
 use core::fmt;
 /// Things shared from day to day
-/// A lot of this was copied from week1.rs
-/// I have opted not to refactor week1 to use these utils, though.
-use std::{ cell::Cell, fs, slice::Iter, usize };
-use colored::{ColoredString, Colorize};
-use itertools::{self, Itertools};
+/// A lot of this was copied from week1.rs, which now uses the grid from here too.
+use std::{ cmp::Ordering, fs, io::Read, ops::{ Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign }, path::Path, slice::Iter, str::FromStr };
 
-pub fn read_input_as_lines(input_filename: &str) -> Vec<String> {
-    let contents = fs
-        ::read_to_string(format!("input/{}", input_filename))
-        .expect("input not found");
-    contents.split("\n").map(String::from).collect()
+use crate::error::{ AocError, AocResult };
+
+/// Reads the whole input file as a single string, for solutions that do their own splitting.
+pub fn read_input(path: &Path) -> AocResult<String> {
+    fs::read_to_string(path).map_err(|err| AocError::new(format!("can't read input: {}", err)).in_file(path))
+}
+
+/// Reads the input file as a vector of strings.
+/// Utility for all aoc problems, as they all begin with a huge text file.
+/// All further processing and formatting is highly dependent on the problem, so it ends there.
+/// Would technically be more efficient to handle a Vec<&str> instead of converting to String objects,
+///     but for re-usability it's a necessary step.
+pub fn read_input_as_lines(path: &Path) -> AocResult<Vec<String>> {
+    let contents = read_input(path)?;
+    Ok(contents.split("\n").map(String::from).collect())
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// A position on a grid, or the offset between two positions.
+/// y grows downwards, so "clockwise" below means clockwise as drawn on screen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Coord {
     pub x: i32,
     pub y: i32,
 }
 
+impl From<(i32, i32)> for Coord {
+    fn from((x, y): (i32, i32)) -> Coord {
+        Coord { x, y }
+    }
+}
+
+impl From<Coord> for (i32, i32) {
+    fn from(coord: Coord) -> (i32, i32) {
+        (coord.x, coord.y)
+    }
+}
+
 impl Coord {
+    pub const fn new(x: i32, y: i32) -> Coord {
+        Coord { x, y }
+    }
+
     pub fn from_index(x: usize, y: usize) -> Coord {
         // Helper constructor to handle enumerator usize to i32 constructions
         Coord {
@@ -27,9 +52,105 @@ impl Coord {
             y: y as i32,
         }
     }
+
+    /// Steps needed to get to `other` moving only orthogonally.
+    pub fn manhattan(&self, other: Coord) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Steps needed to get to `other` when diagonal moves are allowed.
+    pub fn chebyshev(&self, other: Coord) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// The smallest whole step in the same direction as this offset, e.g. (4, -6) becomes (2, -3).
+    /// Walking from a point in these steps visits every grid point on the line through it.
+    pub fn reduced(&self) -> Coord {
+        fn gcd(a: i32, b: i32) -> i32 {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+        match gcd(self.x.abs(), self.y.abs()) {
+            0 => *self,
+            divisor => Coord { x: self.x / divisor, y: self.y / divisor },
+        }
+    }
+
+    /// Rotated a quarter turn clockwise about the origin, e.g. N (0, -1) becomes E (1, 0).
+    pub fn rotate_cw(&self) -> Coord {
+        Coord { x: -self.y, y: self.x }
+    }
+
+    /// Rotated a quarter turn counter-clockwise about the origin, e.g. N (0, -1) becomes W (-1, 0).
+    pub fn rotate_ccw(&self) -> Coord {
+        Coord { x: self.y, y: -self.x }
+    }
+
+    /// The same position on a `width` by `height` torus, brought back into 0..width and 0..height.
+    /// E.g. (-1, 7) on a 10 by 5 grid is (9, 2). Both sizes must be non-zero.
+    pub fn wrapped(&self, width: usize, height: usize) -> Coord {
+        Coord { x: self.x.rem_euclid(width as i32), y: self.y.rem_euclid(height as i32) }
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, other: Coord) -> Coord {
+        Coord { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl AddAssign for Coord {
+    fn add_assign(&mut self, other: Coord) {
+        *self = *self + other;
+    }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, other: Coord) -> Coord {
+        Coord { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl SubAssign for Coord {
+    fn sub_assign(&mut self, other: Coord) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+
+    fn neg(self) -> Coord {
+        Coord { x: -self.x, y: -self.y }
+    }
+}
+
+impl Mul<i32> for Coord {
+    type Output = Coord;
+
+    fn mul(self, factor: i32) -> Coord {
+        Coord { x: self.x * factor, y: self.y * factor }
+    }
+}
+
+/// Reading order: top to bottom, then left to right, same as `Grid::iter`.
+impl Ord for Coord {
+    fn cmp(&self, other: &Coord) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Coord {
+    fn partial_cmp(&self, other: &Coord) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compass directions, with N pointing up the grid (towards smaller y).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
     N,
     NE,
@@ -40,8 +161,22 @@ pub enum Direction {
     W,
     NW,
 }
+
+/// Every direction clockwise from N, in declaration order, so turning is just moving along this list.
+static CLOCKWISE: [Direction; 8] = [
+    Direction::N,
+    Direction::NE,
+    Direction::E,
+    Direction::SE,
+    Direction::S,
+    Direction::SW,
+    Direction::W,
+    Direction::NW,
+];
+
 impl Direction {
-    fn coord_shift(&self) -> Coord {
+    /// How far one step in this direction moves you.
+    pub fn delta(&self) -> Coord {
         match *self {
             Direction::N => Coord { x: 0, y: -1 },
             Direction::NE => Coord { x: 1, y: -1 },
@@ -54,131 +189,722 @@ impl Direction {
         }
     }
 
-    fn jump_cell(&self, origin: Coord) -> Coord {
-        let modify_coord = self.coord_shift();
-        Coord {
-            x: origin.x + modify_coord.x,
-            y: origin.y + modify_coord.y,
-        }
+    /// The direction whose single step is exactly `delta`, if there is one.
+    pub fn from_delta(delta: Coord) -> Option<Direction> {
+        CLOCKWISE.iter().find(|direction| direction.delta() == delta).copied()
     }
 
-    fn rotate_90(&self) -> Option<Direction> {
-        // This could probably be done with iterator followed by rotate_left or right, but opting not to do that for now.
-        match self {
-            Direction::N => Some(Direction::E),
-            Direction::E => Some(Direction::S),
-            Direction::S => Some(Direction::W),
-            Direction::W => Some(Direction::N),
+    /// Reads the arrows, compass letters and up/down/left/right letters puzzles use.
+    /// `^>v<`, `NESW` and `UDLR`, upper or lower case.
+    pub fn from_char(symbol: char) -> Option<Direction> {
+        match symbol.to_ascii_uppercase() {
+            '^' | 'N' | 'U' => Some(Direction::N),
+            '>' | 'E' | 'R' => Some(Direction::E),
+            'V' | 'S' | 'D' => Some(Direction::S),
+            '<' | 'W' | 'L' => Some(Direction::W),
             _ => None,
         }
     }
+
+    /// The cell one step away from `origin` in this direction.
+    pub fn jump_cell(&self, origin: Coord) -> Coord {
+        origin + self.delta()
+    }
+
+    /// Turned clockwise by `eighths` of a full turn.
+    fn turned(&self, eighths: usize) -> Direction {
+        CLOCKWISE[(*self as usize + eighths) % 8]
+    }
+
+    /// 90° clockwise.
+    pub fn turn_right(&self) -> Direction {
+        self.turned(2)
+    }
+
+    /// 90° counter-clockwise.
+    pub fn turn_left(&self) -> Direction {
+        self.turned(6)
+    }
+
+    /// 45° clockwise, e.g. N to NE.
+    pub fn turn_right_45(&self) -> Direction {
+        self.turned(1)
+    }
+
+    /// 45° counter-clockwise, e.g. N to NW.
+    pub fn turn_left_45(&self) -> Direction {
+        self.turned(7)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.turned(4)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, Direction::NE | Direction::SE | Direction::SW | Direction::NW)
+    }
+
     pub fn iterator() -> Iter<'static, Direction> {
-        static DIRECTIONS: [Direction; 8] = [
-            Direction::N,
-            Direction::NE,
-            Direction::E,
-            Direction::SE,
-            Direction::S,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-        ];
+        CLOCKWISE.iter()
+    }
+
+    /// Just N, E, S and W, for puzzles where diagonal moves don't count.
+    pub fn orthogonal() -> Iter<'static, Direction> {
+        static DIRECTIONS: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
         DIRECTIONS.iter()
     }
 }
-#[derive(Clone, Copy, Debug)]
+
+/// Parses a single direction character (see `from_char`) or a compass name like `NE`.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Direction, String> {
+        let mut chars = raw.chars();
+        let single = match (chars.next(), chars.next()) {
+            (Some(symbol), None) => Direction::from_char(symbol),
+            _ => None,
+        };
+        single
+            .or_else(|| match raw.to_ascii_uppercase().as_str() {
+                "NE" => Some(Direction::NE),
+                "SE" => Some(Direction::SE),
+                "SW" => Some(Direction::SW),
+                "NW" => Some(Direction::NW),
+                _ => None,
+            })
+            .ok_or_else(|| format!("'{}' is not a direction", raw))
+    }
+}
+
+/// One character of a puzzle map. Anything else known about a cell lives in `layers::Layers`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Node {
     pub symbol: char,
-    pub marked: bool,
-}
-
-#[derive(Clone, Debug)]
-pub struct Grid {
-    pub height: i32,
-    pub width: i32,
-    pub cells: Vec<Vec<Node>>,
-}
-
-impl Grid {
-    pub fn build_from_file(filename: &str) -> Grid {
-        let lines = read_input_as_lines(filename);
-        let cells: Vec<Vec<Node>> = lines
-            .into_iter()
-            .map(|row: String| {
-                // Each row becomes its own vector of usize, for following calculations
-                row.chars()
-                    .map(|symbol: char| Node { symbol: symbol, marked: false })
-                    .collect()
-            })
-            .filter(|report: &Vec<Node>| report.len() > 0)
+}
+
+impl From<char> for Node {
+    fn from(symbol: char) -> Node {
+        Node { symbol }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol)
+    }
+}
+
+/// What happens to positions past the edge of a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Edges {
+    /// There is nothing there: lookups give `None` and neighbors stop at the edge.
+    #[default]
+    Bounded,
+    /// The grid is a torus: walking off one edge comes back in on the opposite one.
+    Wrapping,
+}
+
+/// A rectangular grid of cells, stored row by row in one flat vector.
+/// Cells are addressed by `Coord`, with (0, 0) in the top left and y growing downwards.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    edges: Edges,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid { width, height, cells: vec![fill; width * height], edges: Edges::Bounded }
+    }
+
+    /// A `width` by `height` grid where each cell is `f` of its position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coord) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Coord::from_index(x, y)))
+            .map(&mut f)
             .collect();
+        Grid { width, height, cells, edges: Edges::Bounded }
+    }
+
+    /// Builds a grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> AocResult<Grid<T>> {
+        let width = rows.first().ok_or_else(|| AocError::new("the grid is empty"))?.len();
+        if let Some(row_index) = rows.iter().position(|row| row.len() != width) {
+            return Err(AocError::new(format!(
+                "row {} has {} cells, expected {}", row_index + 1, rows[row_index].len(), width
+            )));
+        }
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            edges: Edges::Bounded,
+        })
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn edges(&self) -> Edges {
+        self.edges
+    }
+
+    /// The same grid with lookups past its edges handled as `edges` says.
+    /// Grids start out `Edges::Bounded`.
+    pub fn with_edges(mut self, edges: Edges) -> Grid<T> {
+        self.edges = edges;
+        self
+    }
+
+    /// True if `position` is actually on the grid, whatever the edge mode.
+    pub fn in_bounds(&self, position: Coord) -> bool {
+        usize::try_from(position.x).is_ok_and(|x| x < self.width)
+            && usize::try_from(position.y).is_ok_and(|y| y < self.height)
+    }
+
+    /// The on-grid position `position` refers to: itself when in bounds,
+    /// wrapped around when the grid wraps, and `None` otherwise.
+    pub fn normalize(&self, position: Coord) -> Option<Coord> {
+        match self.edges {
+            _ if self.in_bounds(position) => Some(position),
+            Edges::Wrapping if !self.cells.is_empty() => Some(position.wrapped(self.width, self.height)),
+            _ => None,
+        }
+    }
+
+    /// Where `position` lives in `cells`, if it refers to a cell at all.
+    /// Negative coordinates are rejected (or wrapped) by `normalize` rather than wrapped around by a cast.
+    fn index_of(&self, position: &Coord) -> Option<usize> {
+        let position = self.normalize(*position)?;
+        Some(position.y as usize * self.width + position.x as usize)
+    }
+
+    fn coord_of(&self, index: usize) -> Coord {
+        Coord::from_index(index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, position: Coord) -> Option<&T> {
+        self.index_of(&position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Coord) -> Option<&mut T> {
+        self.index_of(&position).map(|index| &mut self.cells[index])
+    }
+
+    /// Replaces the cell at `position`, handing back what was there.
+    /// Off the grid nothing changes and `None` comes back.
+    pub fn set(&mut self, position: Coord, value: T) -> Option<T> {
+        self.get_mut(position).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// The cells one step from `position` in each of `directions`, skipping any off the grid.
+    /// On a wrapping grid every direction has a neighbor, and its position comes back already wrapped.
+    fn neighbors_in(&self, position: Coord, directions: Iter<'static, Direction>) -> impl Iterator<Item = (Direction, Coord, &T)> {
+        directions.filter_map(move |direction| {
+            let neighbor = self.normalize(direction.jump_cell(position))?;
+            Some((*direction, neighbor, &self[neighbor]))
+        })
+    }
+
+    /// The up to 4 orthogonal neighbors of `position`.
+    pub fn neighbors4(&self, position: Coord) -> impl Iterator<Item = (Direction, Coord, &T)> {
+        self.neighbors_in(position, Direction::orthogonal())
+    }
+
+    /// The up to 8 neighbors of `position`, diagonals included.
+    pub fn neighbors8(&self, position: Coord) -> impl Iterator<Item = (Direction, Coord, &T)> {
+        self.neighbors_in(position, Direction::iterator())
+    }
+
+    /// The orthogonal neighbors of `position` whose cell passes `predicate`,
+    /// e.g. the cells a flood fill or a walker may step onto.
+    pub fn neighbors_where(&self, position: Coord, mut predicate: impl FnMut(&T) -> bool) -> impl Iterator<Item = (Direction, Coord, &T)> {
+        self.neighbors4(position).filter(move |(_, _, cell)| predicate(cell))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells.iter().enumerate().map(|(index, cell)| (self.coord_of(index), cell))
+    }
+
+    /// Every cell with its position, row by row, moved out of the grid.
+    pub fn into_cells(self) -> impl Iterator<Item = (Coord, T)> {
+        let width = self.width;
+        self.cells.into_iter().enumerate().map(move |(index, cell)| (Coord::from_index(index % width, index / width), cell))
+    }
+
+    /// The cells of row `y`, left to right. Empty if there is no such row.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = (Coord, &T)> {
+        let cells = if y < self.height { &self.cells[y * self.width..(y + 1) * self.width] } else { &[] };
+        cells.iter().enumerate().map(move |(x, cell)| (Coord::from_index(x, y), cell))
+    }
+
+    /// The cells of column `x`, top to bottom. Empty if there is no such column.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = (Coord, &T)> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| (Coord::from_index(x, y), &self.cells[y * self.width + x]))
+    }
+
+    /// Every cell without its position, row by row.
+    pub fn cells(&self) -> Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Each row as a slice, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
 
-        let grid_height: i32 = (cells.len() as i32) - 1;
-        let grid_width: i32 = (cells.first().unwrap().len() as i32) - 1;
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            edges: self.edges,
+        }
+    }
 
+    /// Like `map`, but `f` is also told where each cell is.
+    pub fn map_with_coord<U>(&self, mut f: impl FnMut(Coord, &T) -> U) -> Grid<U> {
         Grid {
-            height: grid_height,
-            width: grid_width,
-            cells: cells,
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(position, cell)| f(position, cell)).collect(),
+            edges: self.edges,
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    /// Panics when `position` is off the grid, like indexing a `Vec` would. Wrapping grids wrap instead.
+    fn index(&self, position: Coord) -> &T {
+        let index = self.index_of(&position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position));
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, position: Coord) -> &mut T {
+        let index = self.index_of(&position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position));
+        &mut self.cells[index]
+    }
+}
+
+/// One cell per character, one row per line.
+/// Blank lines around the grid are ignored; every other line must be as wide as the first.
+impl<T: From<char>> FromStr for Grid<T> {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> AocResult<Grid<T>> {
+        let lines: Vec<(usize, &str)> = input.lines().enumerate().collect();
+        let first = lines.iter().position(|(_, line)| !line.is_empty()).unwrap_or(lines.len());
+        let last = lines.iter().rposition(|(_, line)| !line.is_empty()).map_or(first, |last| last + 1);
+        let lines = &lines[first..last];
+
+        let width = lines.first().map_or(0, |(_, line)| line.chars().count());
+        if let Some((line_index, line)) = lines.iter().find(|(_, line)| line.chars().count() != width) {
+            return Err(AocError::at_line(
+                *line_index,
+                line,
+                format!("row is {} wide, expected {} like the first row", line.chars().count(), width)
+            ));
+        }
+        Grid::from_rows(lines.iter().map(|(_, line)| line.chars().map(T::from).collect()).collect())
+    }
+}
+
+impl<T: From<char>> Grid<T> {
+    /// Reads a whole grid out of `reader`, e.g. a file or stdin.
+    pub fn from_reader(mut reader: impl Read) -> AocResult<Grid<T>> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|err| AocError::new(format!("can't read grid: {}", err)))?;
+        input.parse()
+    }
+}
+
+impl Grid<Node> {
+    pub fn build_from_file(path: &Path) -> AocResult<Grid<Node>> {
+        let input = read_input(path)?;
+        input.parse().map_err(|err: AocError| err.in_file(path))
+    }
+
+    pub fn build_from_input(input: &str) -> AocResult<Grid<Node>> {
+        input.parse()
+    }
+}
+
+/// The cells as they are, one row per line. `render::Renderer` can add color, layers and rulers.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    fn small() -> Grid<char> {
+        Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]).unwrap()
+    }
+
+    #[test]
+    fn ragged_rows_point_at_the_odd_row() {
+        let error = "\n..#\n#.\n...".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.source_line.as_deref(), Some("#."));
+        assert_eq!(error.message, "row is 2 wide, expected 3 like the first row");
+    }
+
+    #[test]
+    fn surrounding_blank_lines_are_ignored() {
+        let grid: Grid<char> = "\n\n..#\n#..\n\n".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Coord::new(2, 0)), Some(&'#'));
+        assert_eq!(grid.get(Coord::new(0, 1)), Some(&'#'));
+        assert_eq!(grid.to_string(), "..#\n#..\n");
+    }
+
+    #[test]
+    fn blank_lines_inside_the_grid_are_ragged() {
+        let error = "..#\n\n#..".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(error.line, Some(2));
+    }
+
+    #[test]
+    fn empty_input_is_not_a_grid() {
+        for input in ["", "\n\n"] {
+            let error = input.parse::<Grid<char>>().unwrap_err();
+            assert_eq!(error.message, "the grid is empty");
+            assert_eq!(Grid::<char>::from_reader(input.as_bytes()), Err(error));
+        }
+    }
+
+    #[test]
+    fn empty_grid_has_nothing_in_it() {
+        let grid = Grid::new(0, 0, '.');
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.iter().count(), 0);
+        assert_eq!(grid.get(Coord::new(0, 0)), None);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn from_reader_matches_parse() {
+        let input = "#.\n.^\n";
+        let grid: Grid<Node> = Grid::from_reader(input.as_bytes()).unwrap();
+        assert_eq!(grid, input.parse().unwrap());
+        assert_eq!(grid.get(Coord::new(1, 1)), Some(&Node { symbol: '^' }));
+    }
+
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+
+    #[test]
+    fn from_reader_reports_read_errors() {
+        let error = Grid::<char>::from_reader(Broken).unwrap_err();
+        assert_eq!(error.message, "can't read grid: disk on fire");
+    }
+
+    #[test]
+    fn ragged_rows_are_not_a_grid() {
+        let error = Grid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err();
+        assert_eq!(error.to_string(), "row 2 has 1 cells, expected 2");
+        assert!(Grid::<u8>::from_rows(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn cells_are_addressed_by_coord() {
+        let grid = small();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Coord::new(2, 0)), Some(&'c'));
+        assert_eq!(grid[Coord::new(0, 1)], 'd');
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.get(Coord::new(0, 2)), None);
+        assert_eq!(grid.get(Coord::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn get_mut_changes_only_that_cell() {
+        let mut grid = small();
+        *grid.get_mut(Coord::new(1, 1)).unwrap() = 'E';
+        assert!(grid.get_mut(Coord::new(3, 1)).is_none());
+        assert_eq!(grid.cells().collect::<String>(), "abcdEf");
+    }
+
+    #[test]
+    fn map_keeps_the_shape() {
+        let grid = small().map(|cell| cell.to_ascii_uppercase());
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.rows().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>(), ["ABC", "DEF"]);
+    }
+
+    #[test]
+    fn map_with_coord_is_told_where_each_cell_is() {
+        let grid = small().map_with_coord(|position, cell| (position.x, position.y, *cell));
+        assert_eq!(grid[Coord::new(2, 1)], (2, 1, 'f'));
+        assert!(grid.cells().all(|(x, y, cell)| small()[Coord::new(*x, *y)] == *cell));
+    }
+
+    #[test]
+    fn set_hands_back_the_old_cell() {
+        let mut grid = Grid::new(2, 2, '.');
+        assert_eq!(grid.set(Coord::new(1, 0), '#'), Some('.'));
+        assert_eq!(grid.set(Coord::new(2, 0), '#'), None);
+        assert!(grid.in_bounds(Coord::new(1, 1)));
+        assert!(!grid.in_bounds(Coord::new(0, -1)));
+        assert_eq!(grid.cells().collect::<String>(), ".#..");
+    }
+
+    #[test]
+    fn row_runs_left_to_right() {
+        let grid = small();
+        let row: Vec<(Coord, char)> = grid.row(1).map(|(position, cell)| (position, *cell)).collect();
+        assert_eq!(row, [(Coord::new(0, 1), 'd'), (Coord::new(1, 1), 'e'), (Coord::new(2, 1), 'f')]);
+        assert_eq!(grid.row(2).count(), 0);
+    }
+
+    #[test]
+    fn column_runs_top_to_bottom() {
+        let grid = small();
+        let column: Vec<(Coord, char)> = grid.column(2).map(|(position, cell)| (position, *cell)).collect();
+        assert_eq!(column, [(Coord::new(2, 0), 'c'), (Coord::new(2, 1), 'f')]);
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn corners_have_fewer_neighbors() {
+        let grid = small();
+        let directions = |position| grid.neighbors4(position).map(|(direction, _, _)| direction).collect::<Vec<_>>();
+        assert_eq!(directions(Coord::new(0, 0)), [Direction::E, Direction::S]);
+        assert_eq!(grid.neighbors4(Coord::new(1, 0)).count(), 3);
+        assert_eq!(grid.neighbors8(Coord::new(1, 0)).count(), 5);
+        assert_eq!(grid.neighbors8(Coord::new(0, 1)).count(), 3);
+    }
+
+    #[test]
+    fn neighbors_where_keeps_only_passing_cells() {
+        let grid: Grid<char> = "#.#\n...\n#.#".parse().unwrap();
+        let open: Vec<(Direction, Coord, char)> = grid
+            .neighbors_where(Coord::new(1, 0), |cell| *cell == '.')
+            .map(|(direction, position, cell)| (direction, position, *cell))
+            .collect();
+        assert_eq!(open, [(Direction::S, Coord::new(1, 1), '.')]);
+        assert_eq!(grid.neighbors_where(Coord::new(1, 1), |cell| *cell == '#').count(), 0);
+        assert_eq!(grid.neighbors_where(Coord::new(1, 1), |cell| *cell == '.').count(), 4);
+    }
+
+    #[test]
+    fn turning_all_the_way_round_comes_back() {
+        for direction in Direction::iterator() {
+            let left = (0..4).fold(*direction, |facing, _| facing.turn_left());
+            let right_45 = (0..8).fold(*direction, |facing, _| facing.turn_right_45());
+            assert_eq!(left, *direction);
+            assert_eq!(right_45, *direction);
+            assert_eq!(direction.turn_right().turn_left(), *direction);
+            assert_eq!(direction.turn_left_45().turn_right_45(), *direction);
         }
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::N.turn_left_45(), Direction::NW);
     }
 
-    pub fn get_cell(&self, position: Coord) -> Option<&Node> {
-        let x = self.cells
-            .get(position.y as usize)
-            .map(|row| row.get(position.x as usize))
-            .flatten();
+    #[test]
+    fn opposite_twice_is_where_you_started() {
+        for direction in Direction::iterator() {
+            assert_ne!(direction.opposite(), *direction);
+            assert_eq!(direction.opposite().opposite(), *direction);
+            assert_eq!(direction.opposite().is_diagonal(), direction.is_diagonal());
+        }
+        assert_eq!(Direction::NE.opposite(), Direction::SW);
+    }
 
-        x
+    #[test]
+    fn from_delta_undoes_delta() {
+        for direction in Direction::iterator() {
+            assert_eq!(Direction::from_delta(direction.delta()), Some(*direction));
+        }
+        assert_eq!(Direction::from_delta(Coord::new(0, 0)), None);
+        assert_eq!(Direction::from_delta(Coord::new(2, 0)), None);
     }
 
-    pub fn mark_cell(&mut self, position: Coord) -> bool {
-        match self.get_cell(position.clone()) {
-            Some(Node { symbol, marked: false }) => {
-                // If get_cell actually gets something, positions must be convertable to usize by definition.
-                self.cells[position.y as usize][position.x as usize] = Node {
-                    symbol: *symbol,
-                    marked: true,
-                };
-                true
+    #[test]
+    fn directions_parse_from_arrows_and_letters() {
+        for (symbols, direction) in [("^NnUu", Direction::N), (">EeRr", Direction::E), ("vVSsDd", Direction::S), ("<WwLl", Direction::W)] {
+            for symbol in symbols.chars() {
+                assert_eq!(Direction::from_char(symbol), Some(direction), "{}", symbol);
+                assert_eq!(symbol.to_string().parse(), Ok(direction));
             }
-            _ => false,
-        }
-    }
-}
-
-impl fmt::Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.cells
-            .iter()
-            .for_each(|row| {
-                row.iter().for_each(|node| {
-                    if node.marked {
-                        write!(f, "{}", node.symbol.to_string().on_red());
-                    } else {
-                        write!(f, "{}", node.symbol.to_string().green());
-                    }
-                });
-                vec![writeln!(f, "")];
-                
-            });
-            Ok(())            
-        }
-        // Below is a safer, smarter implementation that I couldn't get to work, but leaving for potential future use.
-/*     fn __fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.cells
-            .iter()
-            .map(|row| {
-                let outline = row.iter().map(|node| {
-                    if node.marked {
-                        write!(f, "{}", node.symbol.to_string().red())
-                    } else {
-                        write!(f, "{}", node.symbol.to_string().green())
-                    }
-                });
-                outline.chain(vec![writeln!(f, "")]).collect()
-                
-            }).collect()
-        }
-        */
+        }
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!(Direction::from_char('.'), None);
+        assert_eq!("ne".parse(), Ok(Direction::NE));
+        assert_eq!("SW".parse(), Ok(Direction::SW));
+        assert_eq!("x".parse::<Direction>(), Err("'x' is not a direction".to_string()));
+        assert!("".parse::<Direction>().is_err());
+        assert!("NNE".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn reduced_keeps_the_direction_of_an_offset() {
+        assert_eq!(Coord::new(4, -6).reduced(), Coord::new(2, -3));
+        assert_eq!(Coord::new(-3, -9).reduced(), Coord::new(-1, -3));
+        assert_eq!(Coord::new(0, -5).reduced(), Coord::new(0, -1));
+        assert_eq!(Coord::new(7, 0).reduced(), Coord::new(1, 0));
+        assert_eq!(Coord::new(0, 0).reduced(), Coord::new(0, 0));
+        assert_eq!(Coord::new(3, 5).reduced(), Coord::new(3, 5));
+    }
+
+    #[test]
+    fn rotations_undo_each_other() {
+        for direction in Direction::iterator() {
+            let delta = direction.delta();
+            assert_eq!(delta.rotate_ccw().rotate_cw(), delta);
+            assert_eq!(delta.rotate_cw().rotate_ccw(), delta);
+            assert_eq!(delta.rotate_cw(), direction.turn_right().delta());
+            assert_eq!(delta.rotate_ccw(), direction.turn_left().delta());
+        }
+    }
+
+    #[test]
+    fn distances_count_steps() {
+        let from = Coord::new(1, -2);
+        assert_eq!(from.manhattan(Coord::new(4, 2)), 7);
+        assert_eq!(from.chebyshev(Coord::new(4, 2)), 4);
+        assert_eq!(Coord::new(4, 2).manhattan(from), 7);
+        assert_eq!(from.manhattan(from), 0);
+        assert_eq!(from.chebyshev(Coord::new(-2, -5)), 3);
+    }
+
+    #[test]
+    fn coords_sort_in_reading_order() {
+        let mut positions = vec![Coord::new(0, 1), Coord::new(2, 0), Coord::new(-1, 1), Coord::new(0, 0)];
+        positions.sort();
+        assert_eq!(positions, [Coord::new(0, 0), Coord::new(2, 0), Coord::new(-1, 1), Coord::new(0, 1)]);
+        let grid = small();
+        let order: Vec<Coord> = grid.iter().map(|(position, _)| position).collect();
+        assert!(order.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn coords_do_arithmetic() {
+        let mut position = Coord::new(3, -1);
+        assert_eq!(position + Coord::new(1, 2), Coord::new(4, 1));
+        assert_eq!(position - Coord::new(1, 2), Coord::new(2, -3));
+        assert_eq!(-position, Coord::new(-3, 1));
+        assert_eq!(position * 3, Coord::new(9, -3));
+        assert_eq!(position * -1, -position);
+        position += Coord::new(-3, 1);
+        assert_eq!(position, Coord::new(0, 0));
+        position -= Direction::SE.delta();
+        assert_eq!(position, Coord::new(-1, -1));
+        assert_eq!(Coord::from((5, 6)), Coord::new(5, 6));
+        assert_eq!(<(i32, i32)>::from(Coord::new(5, 6)), (5, 6));
+    }
+
+    #[test]
+    fn wrapped_coords_land_on_the_torus() {
+        assert_eq!(Coord::new(-1, 7).wrapped(10, 5), Coord::new(9, 2));
+        assert_eq!(Coord::new(10, 5).wrapped(10, 5), Coord::new(0, 0));
+        assert_eq!(Coord::new(-20, -6).wrapped(10, 5), Coord::new(0, 4));
+        assert_eq!(Coord::new(3, 4).wrapped(10, 5), Coord::new(3, 4));
+    }
+
+    fn torus() -> Grid<char> {
+        "abc\ndef".parse::<Grid<char>>().unwrap().with_edges(Edges::Wrapping)
+    }
+
+    #[test]
+    fn wrapping_normalizes_any_coordinate() {
+        let grid = torus();
+        assert_eq!(grid.normalize(Coord::new(1, 1)), Some(Coord::new(1, 1)));
+        assert_eq!(grid.normalize(Coord::new(-1, 0)), Some(Coord::new(2, 0)));
+        assert_eq!(grid.normalize(Coord::new(0, -1)), Some(Coord::new(0, 1)));
+        assert_eq!(grid.normalize(Coord::new(-4, -3)), Some(Coord::new(2, 1)));
+        assert_eq!(grid.normalize(Coord::new(3001, 1000)), Some(Coord::new(1, 0)));
+        assert_eq!(grid.normalize(Coord::new(-3001, -1001)), Some(Coord::new(2, 1)));
+        assert_eq!(grid.get(Coord::new(-1, -1)), Some(&'f'));
+        assert_eq!(grid[Coord::new(5, 2)], 'c');
+    }
+
+    #[test]
+    fn bounded_grids_refuse_far_out_coordinates() {
+        let grid = torus().with_edges(Edges::Bounded);
+        assert_eq!(grid.normalize(Coord::new(-1, 0)), None);
+        assert_eq!(grid.normalize(Coord::new(3, 0)), None);
+        assert_eq!(grid.get(Coord::new(0, -1)), None);
+        assert!(!grid.in_bounds(Coord::new(-1, 0)));
+    }
+
+    #[test]
+    fn setting_a_wrapped_coordinate_sets_the_cell_it_wraps_to() {
+        let mut grid = torus();
+        grid.set(Coord::new(-1, 2), '#');
+        assert_eq!(grid.to_string(), "ab#\ndef\n");
+    }
+
+    fn neighbors(grid: &Grid<char>, position: Coord) -> Vec<(Coord, char)> {
+        let mut neighbors: Vec<(Coord, char)> = grid.neighbors4(position).map(|(_, neighbor, cell)| (neighbor, *cell)).collect();
+        neighbors.sort();
+        neighbors
+    }
+
+    #[test]
+    fn neighbors_wrap_around_the_corners() {
+        let grid = torus();
+        assert_eq!(neighbors(&grid, Coord::new(0, 0)), vec![
+            (Coord::new(1, 0), 'b'),
+            (Coord::new(2, 0), 'c'),
+            (Coord::new(0, 1), 'd'),
+            (Coord::new(0, 1), 'd'),
+        ]);
+        assert_eq!(neighbors(&grid, Coord::new(2, 1)), vec![
+            (Coord::new(2, 0), 'c'),
+            (Coord::new(2, 0), 'c'),
+            (Coord::new(0, 1), 'd'),
+            (Coord::new(1, 1), 'e'),
+        ]);
+        assert_eq!(grid.neighbors8(Coord::new(0, 0)).count(), 8);
+    }
+
+    #[test]
+    fn bounded_neighbors_stop_at_the_corners() {
+        let grid = torus().with_edges(Edges::Bounded);
+        assert_eq!(neighbors(&grid, Coord::new(0, 0)), vec![(Coord::new(1, 0), 'b'), (Coord::new(0, 1), 'd')]);
+        assert_eq!(grid.neighbors8(Coord::new(2, 1)).count(), 3);
+    }
 }
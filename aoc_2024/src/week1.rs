@@ -1,70 +1,120 @@
-use std::{ collections::HashMap, f32::consts::E, fs, hash::Hash, iter, result, slice::Iter, usize };
+use std::{ collections::HashMap, path::Path };
 use regex::{ Regex, Match };
 use std::cmp::Ordering;
 
-fn insert_str_as_int_in_order(ordered_vec: &mut Vec<i32>, new_item: &str) {
-    let parsed = new_item.parse::<i32>().unwrap();
+use crate::error::{ self, AocError, AocResult };
+use crate::layers::Layers;
+use crate::solution::{ Answer, Solution };
+use crate::utils::{ read_input_as_lines, Coord, Direction, Grid, Node };
+
+fn insert_str_as_int_in_order(ordered_vec: &mut Vec<i32>, new_item: &str, line_index: usize, line: &str) -> AocResult<()> {
+    let parsed = error::parse_number::<i32>(line_index, line, new_item.trim())?;
     let position = ordered_vec.binary_search(&parsed).unwrap_or_else(|err| err);
     ordered_vec.insert(position, parsed);
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct Day1 {
+    left_col: Vec<i32>,
+    right_col: Vec<i32>,
 }
 
-/// Reads the input file as a vector of strings.
-/// Utility for all aoc problems, as they all begin with a huge text file.
-/// All further processing and formatting is highly dependent on the problem, so it ends there.
-/// Would technically be more efficient to handle a Vec<&str> instead of converting to String objects,
-///     but for re-usability it's a necessary step.
-fn read_input_as_lines(input_filename: &str) -> Vec<String> {
-    let contents = fs
-        ::read_to_string(format!("input/{}", input_filename))
-        .expect("input not found");
-    contents.split("\n").map(String::from).collect()
-}
-
-pub fn day1() {
-    let lines = read_input_as_lines("day1_input.txt");
-    // Mutable vectors for ease of building from parsed input
-    let mut left_col: Vec<i32> = Vec::new();
-    let mut right_col: Vec<i32> = Vec::new();
-
-    for line in lines.iter() {
-        line.split_once("   ").map(|(l, r)| {
-            insert_str_as_int_in_order(&mut left_col, l);
-            insert_str_as_int_in_order(&mut right_col, r);
+impl Solution for Day1 {
+    fn parse(&mut self, input: &str) -> AocResult<()> {
+        // Mutable vectors for ease of building from parsed input
+        for (line_index, line) in input.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let (l, r) = line
+                .split_once("   ")
+                .ok_or_else(|| AocError::at_line(line_index, line, "expected two numbers separated by three spaces"))?;
+            insert_str_as_int_in_order(&mut self.left_col, l, line_index, line)?;
+            insert_str_as_int_in_order(&mut self.right_col, r, line_index, line)?;
+        }
+        Ok(())
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[include_str!("../input/day1_example1.txt")]
+    }
+
+    fn part1(&self) -> AocResult<Answer> {
+        let distances = self.left_col
+            .iter()
+            .zip(self.right_col.iter())
+            .map(|(l, r)| (l - r).abs());
+        let distances_sum: i32 = distances.sum();
+        Ok(distances_sum.into())
+    }
+
+    fn part2(&self) -> AocResult<Answer> {
+        // Keeps pre-calculated hashmap in case there lots of duplicates in the input that don't need to be re-calculated every time.
+        let mut calculated: HashMap<&i32, usize> = HashMap::new();
+        let similarity = self.left_col.iter().map(|item| {
+            // get() returns an Optional reference
+            // map() dereferences that reference
+            // unwrwap_or_else calculates the new usize in the event of a None from get()
+            //    This dance is required in order to keep the expression as one type: a dereferences `usize`.
+            let multipler: usize = calculated
+                .get(item)
+                .copied()
+                .unwrap_or_else(|| {
+                    let &count = &self.right_col
+                        .iter()
+                        .filter(|&n| *n == *item)
+                        .count();
+                    count
+                });
+            calculated.insert(item, multipler);
+            item * (multipler as i32)
         });
+        let similarity_sum: i32 = similarity.sum();
+        Ok(similarity_sum.into())
     }
+}
 
-    let distances = left_col
-        .iter()
-        .zip(right_col.iter())
-        .map(|(l, r)| (l - r).abs());
-
-    // Keeps pre-calculated hashmap in case there lots of duplicates in the input that don't need to be re-calculated every time.
-    let mut calculated: HashMap<&i32, usize> = HashMap::new();
-    let similarity = left_col.iter().map(|item| {
-        // get() returns an Optional reference
-        // map() dereferences that reference
-        // unwrwap_or_else calculates the new usize in the event of a None from get()
-        //    This dance is required in order to keep the expression as one type: a dereferences `usize`.
-        let multipler: usize = calculated
-            .get(item)
-            .map(|item| *item)
-            .unwrap_or_else(|| {
-                let &count = &right_col
-                    .iter()
-                    .filter(|&n| *n == *item)
-                    .count();
-                count
+struct ReportSafety {
+    is_safe: bool,
+    previous_room: Option<i32>,
+    is_ascending: Option<bool>,
+}
+
+struct EvaluatedReport {
+    report: Vec<i32>,
+    report_safety: ReportSafety,
+}
+
+fn is_report_safe(report: Vec<i32>) -> ReportSafety {
+    report.iter().fold(
+        ReportSafety {
+            is_safe: true,
+            previous_room: None,
+            is_ascending: None,
+        },
+        |safety_report, room| {
+            let new_safety_report = safety_report.previous_room.map(|previous_room| {
+                let is_ascending = previous_room < *room;
+                let room_diff = (previous_room - room).abs();
+                let room_diff_safe = (1..=3).contains(&room_diff);
+                let ascention_safe = safety_report.is_ascending
+                    .map(|b| b == is_ascending)
+                    .unwrap_or(true);
+                // The new report compounds on the previous report; Safety never switch false to true, but could switch False here.
+                // The previous room is set to the current processing room, for the next iteration of this fold.
+                let is_safe = room_diff_safe && ascention_safe && safety_report.is_safe;
+                ReportSafety {
+                    is_safe,
+                    previous_room: Some(*room),
+                    is_ascending: Some(is_ascending),
+                }
             });
-        calculated.insert(item, multipler);
-        item * (multipler as i32)
-    });
-    let distances_sum: i32 = distances.sum();
-    println!("Part 1 (Distances): {}", distances_sum);
-    let similarity_sum: i32 = similarity.sum();
-    println!("Part 2 (Similarity Score): {}", similarity_sum);
 
-    assert_eq!(distances_sum, 1722302);
-    assert_eq!(similarity_sum, 20373490);
+            new_safety_report.unwrap_or(ReportSafety {
+                is_safe: safety_report.is_safe,
+                previous_room: Some(*room),
+                is_ascending: safety_report.is_ascending,
+            })
+        }
+    )
 }
 
 /// Day2 Rules
@@ -74,180 +124,170 @@ pub fn day1() {
 ///     1. The entire row is either deacreasing or increasing (1 > 2 > 3 or 3 > 2 > 1, never 2 > 3 > 1)
 ///     2. Each column of the row differs by at least 1 from the previous
 ///     3. Each column of the row differs by at most 3 from the previous
-pub fn day2() {
-    let lines = read_input_as_lines("day2_input.txt");
-    // TODO:: These collects are wasteful -- we we should be able to hand the raw streams around...
-    let reports: Vec<Vec<i32>> = lines
-        .into_iter()
-        .map(|row: String| {
-            // Each row becomes its own vector of usize, for following calculations
-            row.split_whitespace()
-                .flat_map(|room: &str| room.parse::<i32>())
-                .collect()
-        })
-        .filter(|report: &Vec<i32>| report.len() > 0)
-        .collect();
+#[derive(Default)]
+pub struct Day2 {
+    reports: Vec<Vec<i32>>,
+}
 
-    struct ReportSafety {
-        is_safe: bool,
-        previous_room: Option<i32>,
-        is_ascending: Option<bool>,
-    }
-
-    struct EvaluatedReport {
-        report: Vec<i32>,
-        report_safety: ReportSafety,
-    }
-
-    fn is_report_safe(report: Vec<i32>) -> ReportSafety {
-        report.iter().fold(
-            ReportSafety {
-                is_safe: true,
-                previous_room: None,
-                is_ascending: None,
-            },
-            |safety_report, room| {
-                let new_safety_report = safety_report.previous_room.map(|previous_room| {
-                    let is_ascending = previous_room < *room;
-                    let room_diff = (previous_room - room).abs();
-                    let room_diff_safe = 1 <= room_diff && room_diff <= 3;
-                    let ascention_safe = safety_report.is_ascending
-                        .map(|b| b == is_ascending)
-                        .unwrap_or(true);
-                    // The new report compounds on the previous report; Safety never switch false to true, but could switch False here.
-                    // The previous room is set to the current processing room, for the next iteration of this fold.
-                    let is_safe = room_diff_safe && ascention_safe && safety_report.is_safe;
-                    ReportSafety {
-                        is_safe: is_safe,
-                        previous_room: Some(*room),
-                        is_ascending: Some(is_ascending),
-                    }
-                });
+impl Day2 {
+    fn evaluate_reports(&self) -> Vec<EvaluatedReport> {
+        self.reports
+            .iter()
+            .map(|report| {
+                EvaluatedReport {
+                    report: report.clone(),
+                    report_safety: is_report_safe(report.clone()),
+                }
+            })
+            .collect()
+    }
+}
 
-                new_safety_report.unwrap_or(ReportSafety {
-                    is_safe: safety_report.is_safe,
-                    previous_room: Some(*room),
-                    is_ascending: safety_report.is_ascending,
-                })
-            }
-        )
+impl Solution for Day2 {
+    fn parse(&mut self, input: &str) -> AocResult<()> {
+        // TODO:: These collects are wasteful -- we we should be able to hand the raw streams around...
+        self.reports = input
+            .split("\n")
+            .enumerate()
+            .map(|(row_index, row)| {
+                // Each row becomes its own vector of usize, for following calculations
+                row.split_whitespace()
+                    .map(|room: &str| error::parse_number::<i32>(row_index, row, room))
+                    .collect::<AocResult<Vec<i32>>>()
+            })
+            .filter(|report| report.as_ref().map_or(true, |report| !report.is_empty()))
+            .collect::<AocResult<_>>()?;
+        Ok(())
     }
 
-    let num_total_reports = reports.len();
-    let report_safety_metrics: Vec<EvaluatedReport> = reports
-        .into_iter()
-        .map(|report| {
-            EvaluatedReport {
-                report: report.clone(),
-                report_safety: is_report_safe(report),
-            }
-        })
-        .collect();
+    fn examples(&self) -> &'static [&'static str] {
+        &[include_str!("../input/day2_example1.txt")]
+    }
 
-    // Safe when dampened was added after the fact, which is why the EvaluatedReport response structure doesn't really make sense here.
-    // The original goal was to evaluate safe and safe-when-dampened in the same evaluation by maintaining whether or not we had skipped already during the fold.
-    // This didn't work because there are patterns where skipping the unsafe room doesn't make the report safe, but skipping another would.
-    // For instance, 2 1 2 3 4 5: Skipping in-place would skip 1, keeping this report unsafe. Skipping the first 2, however, makes it safe.
-    // The fallback solution here is a dirty hack that just attempts to drop each item from the report one by one until one is potentially marked "safe".
-    // There is almost certainly a smarter solution, but such is life
-    let safe_when_dampened = report_safety_metrics
-        .iter()
-        .filter(|report| !report.report_safety.is_safe)
-        .map(|unsafe_report| {
-            for i in 0..unsafe_report.report.len() {
-                let mut partial_report = unsafe_report.report.clone();
-                partial_report.remove(i);
-                let partial_evaluation = is_report_safe(partial_report.clone());
-                if partial_evaluation.is_safe {
-                    return EvaluatedReport {
-                        report: partial_report,
-                        report_safety: partial_evaluation,
-                    };
-                }
-            }
-            EvaluatedReport {
-                report: unsafe_report.report.clone(),
-                report_safety: ReportSafety {
-                    is_safe: false,
-                    is_ascending: None,
-                    previous_room: None,
-                },
-            }
-        });
+    fn part1(&self) -> AocResult<Answer> {
+        let num_safe_reports = self
+            .evaluate_reports()
+            .iter()
+            .filter(|report| report.report_safety.is_safe)
+            .count();
+        Ok(num_safe_reports.into())
+    }
 
-    let num_safe_reports = report_safety_metrics
-        .iter()
-        .filter(|report| report.report_safety.is_safe)
-        .count();
-    let num_dampened_safe_reports = safe_when_dampened
-        .into_iter()
-        .filter(|report| report.report_safety.is_safe)
-        .count();
+    fn part2(&self) -> AocResult<Answer> {
+        let report_safety_metrics = self.evaluate_reports();
 
-    println!("Num Reports): {}", num_total_reports);
-    println!("Part 1 (Num Safe Reports): {}", num_safe_reports);
-    assert_eq!(num_safe_reports, 526);
-    println!(
-        "Part 2 (Num Dampened Safe Reports): {}",
-        num_dampened_safe_reports + num_safe_reports
-    );
-    // Don't have the answer yet, but know its more than this
-    assert!(num_dampened_safe_reports + num_safe_reports > 552);
-
-    ()
-}
-
-pub fn day3() {
-    let lines = read_input_as_lines("day3_input.txt");
-    let input = lines.join("");
-    let mul_regex = Regex::new(r"mul\(\d{1,3},\d{1,3}\)").unwrap();
-    // let inner_regex = Regex::new(r"\d{1,3}").unwrap();
-    fn multiply_match(m: Match<'_>) -> i32 {
-        let raw: &str = m.as_str();
-        // TODO:: this is a pain in the ass to turn in to a constant available outside fn ref so oh well, eat it performance
-        let inner_regex = Regex::new(r"\d{1,3}").unwrap();
-        inner_regex
-            .find_iter(raw)
-            .fold(1, |result, m| { result * m.as_str().parse::<i32>().unwrap() })
-    }
-
-    let multiplied_values = mul_regex.find_iter(&input).map(multiply_match);
-
-    struct OperationalHoldover {
-        doing: bool,
-        total: i32,
-    }
-
-    // TODO / another option: Named capture matches? But, maybe more complicated than its worth...
-    let doanddont_regex = Regex::new(r"(mul\(\d{1,3},\d{1,3}\))|(do\(\))|(don't\(\))").unwrap();
-    let multipled_values_with_holdover = doanddont_regex
-        .find_iter(&input)
-        .fold(OperationalHoldover { doing: true, total: 0 }, |holdover, m| {
-            let raw: &str = m.as_str();
-            match raw {
-                "do()" => OperationalHoldover { doing: true, total: holdover.total },
-                "don't()" => OperationalHoldover { doing: false, total: holdover.total },
-                _ =>
-                    match holdover.doing {
-                        true =>
-                            OperationalHoldover {
-                                doing: true,
-                                total: holdover.total + multiply_match(m),
-                            },
-                        false => holdover,
+        // Safe when dampened was added after the fact, which is why the EvaluatedReport response structure doesn't really make sense here.
+        // The original goal was to evaluate safe and safe-when-dampened in the same evaluation by maintaining whether or not we had skipped already during the fold.
+        // This didn't work because there are patterns where skipping the unsafe room doesn't make the report safe, but skipping another would.
+        // For instance, 2 1 2 3 4 5: Skipping in-place would skip 1, keeping this report unsafe. Skipping the first 2, however, makes it safe.
+        // The fallback solution here is a dirty hack that just attempts to drop each item from the report one by one until one is potentially marked "safe".
+        // There is almost certainly a smarter solution, but such is life
+        let safe_when_dampened = report_safety_metrics
+            .iter()
+            .filter(|report| !report.report_safety.is_safe)
+            .map(|unsafe_report| {
+                for i in 0..unsafe_report.report.len() {
+                    let mut partial_report = unsafe_report.report.clone();
+                    partial_report.remove(i);
+                    let partial_evaluation = is_report_safe(partial_report.clone());
+                    if partial_evaluation.is_safe {
+                        return EvaluatedReport {
+                            report: partial_report,
+                            report_safety: partial_evaluation,
+                        };
                     }
-            }
-        });
+                }
+                EvaluatedReport {
+                    report: unsafe_report.report.clone(),
+                    report_safety: ReportSafety {
+                        is_safe: false,
+                        is_ascending: None,
+                        previous_room: None,
+                    },
+                }
+            });
+
+        let num_safe_reports = report_safety_metrics
+            .iter()
+            .filter(|report| report.report_safety.is_safe)
+            .count();
+        let num_dampened_safe_reports = safe_when_dampened
+            .filter(|report| report.report_safety.is_safe)
+            .count();
+
+        Ok((num_dampened_safe_reports + num_safe_reports).into())
+    }
+}
+
+fn multiply_match(m: Match<'_>) -> i32 {
+    let raw: &str = m.as_str();
+    // TODO:: this is a pain in the ass to turn in to a constant available outside fn ref so oh well, eat it performance
+    let inner_regex = Regex::new(r"\d{1,3}").unwrap();
+    inner_regex
+        .find_iter(raw)
+        .fold(1, |result, m| { result * m.as_str().parse::<i32>().unwrap() })
+}
 
-    let part_1_sum = multiplied_values.sum::<i32>();
-    println!("Part 1 (sum of mults): {}", part_1_sum);
-    assert_eq!(part_1_sum, 166630675);
-    println!("Part 2 (do's and don'ts): {}", multipled_values_with_holdover.total)
+#[derive(Default)]
+pub struct Day3 {
+    input: String,
+}
+
+impl Solution for Day3 {
+    fn parse(&mut self, input: &str) -> AocResult<()> {
+        self.input = input.split("\n").collect::<Vec<&str>>().join("");
+        Ok(())
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            include_str!("../input/day3_example1.txt"),
+            include_str!("../input/day3_example2.txt"),
+        ]
+    }
+
+    fn part1(&self) -> AocResult<Answer> {
+        let mul_regex = Regex::new(r"mul\(\d{1,3},\d{1,3}\)").unwrap();
+        let multiplied_values = mul_regex.find_iter(&self.input).map(multiply_match);
+        let part_1_sum = multiplied_values.sum::<i32>();
+        Ok(part_1_sum.into())
+    }
+
+    fn part2(&self) -> AocResult<Answer> {
+        struct OperationalHoldover {
+            doing: bool,
+            total: i32,
+        }
+
+        // TODO / another option: Named capture matches? But, maybe more complicated than its worth...
+        let doanddont_regex = Regex::new(r"(mul\(\d{1,3},\d{1,3}\))|(do\(\))|(don't\(\))").unwrap();
+        let multipled_values_with_holdover = doanddont_regex
+            .find_iter(&self.input)
+            .fold(OperationalHoldover { doing: true, total: 0 }, |holdover, m| {
+                let raw: &str = m.as_str();
+                match raw {
+                    "do()" => OperationalHoldover { doing: true, total: holdover.total },
+                    "don't()" => OperationalHoldover { doing: false, total: holdover.total },
+                    _ =>
+                        match holdover.doing {
+                            true =>
+                                OperationalHoldover {
+                                    doing: true,
+                                    total: holdover.total + multiply_match(m),
+                                },
+                            false => holdover,
+                        }
+                }
+            });
+        Ok(multipled_values_with_holdover.total.into())
+    }
 }
 
 const XMAS: &str = "XMAS";
-pub fn _day4_snake() {
-    let lines = read_input_as_lines("day4_input.txt");
+fn _day4_snake() {
+    let Ok(lines) = read_input_as_lines(Path::new("input/day4_input.txt")) else {
+        return;
+    };
     #[derive(Clone, Copy, Debug)]
     struct Node {
         letter: char,
@@ -265,10 +305,10 @@ pub fn _day4_snake() {
         .map(|row: String| {
             // Each row becomes its own vector of usize, for following calculations
             row.chars()
-                .map(|letter: char| Node { letter: letter, visited: false })
+                .map(|letter: char| Node { letter, visited: false })
                 .collect()
         })
-        .filter(|report: &Vec<Node>| report.len() > 0)
+        .filter(|report: &Vec<Node>| !report.is_empty())
         .collect();
 
     let grid_height: i32 = (cells.len() as i32) - 1;
@@ -277,20 +317,19 @@ pub fn _day4_snake() {
     let grid = Grid {
         max_x: grid_height,
         max_y: grid_width,
-        cells: cells,
+        cells,
     };
 
     // The Crawl: Append <the crawl> to the end of the string  (4 chars), return a list of crawls
     fn build_fourpairs(grid: Grid, current_node: (i32, i32), current_word: String) -> Vec<String> {
         println!("{}", current_word);
-        let mut adjacent_nodes: Vec<(usize, usize)> = Vec::new();
+        let _adjacent_nodes: Vec<(usize, usize)> = Vec::new();
         let (x, y) = current_node;
         let current_cell = grid.cells
             .get(y as usize)
-            .map(|row| row.get(x as usize))
-            .flatten();
+            .and_then(|row| row.get(x as usize));
         current_cell
-            .map(|node| {
+            .and_then(|node| {
                 match node.visited {
                     true => None,
                     false => {
@@ -330,8 +369,7 @@ pub fn _day4_snake() {
                     }
                 }
             })
-            .flatten()
-            .unwrap_or(Vec::new())
+            .unwrap_or_default()
     }
 
     let mut all_words: Vec<String> = Vec::new();
@@ -344,597 +382,510 @@ pub fn _day4_snake() {
 
     let count_xmas = all_words
         .into_iter()
-        .filter(|word| *word == String::from("XMAS"))
+        .filter(|word| *word == "XMAS")
         .count();
     println!("Part 1 (count all xmas): {}", count_xmas)
 }
 
-#[derive(Clone, Debug, PartialEq)]
-enum Direction {
-    N,
-    NE,
-    E,
-    SE,
-    S,
-    SW,
-    W,
-    NW,
-}
-impl Direction {
-    fn coord_shift(&self) -> (i32, i32) {
-        match *self {
-            Direction::N => (0, -1),
-            Direction::NE => (1, -1),
-            Direction::E => (1, 0),
-            Direction::SE => (1, 1),
-            Direction::S => (0, 1),
-            Direction::SW => (-1, 1),
-            Direction::W => (-1, 0),
-            Direction::NW => (-1, -1),
-        }
-    }
-    fn jump_cell(&self, origin: (i32, i32)) -> (i32, i32) {
-        let (origin_x, origin_y) = origin;
-        let (modify_x, modify_y) = self.coord_shift();
-        (origin_x + modify_x, origin_y + modify_y)
-    }
-
-    fn rotate_90(&self) -> Option<Direction> {
-        // This could probably be done with iterator followed by rotate_left or right, but opting not to do that for now.
-        match self {
-            Direction::N => Some(Direction::E),
-            Direction::E => Some(Direction::S),
-            Direction::S => Some(Direction::W),
-            Direction::W => Some(Direction::N),
-            _ => None,
-        }
-    }
-    pub fn iterator() -> Iter<'static, Direction> {
-        static DIRECTIONS: [Direction; 8] = [
-            Direction::N,
-            Direction::NE,
-            Direction::E,
-            Direction::SE,
-            Direction::S,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-        ];
-        DIRECTIONS.iter()
-    }
-}
-#[derive(Clone, Copy, Debug)]
-struct Node {
-    letter: char,
-    visited: bool,
-}
-
-#[derive(Clone, Debug)]
-struct Grid {
-    height: i32,
-    width: i32,
-    cells: Vec<Vec<Node>>,
+/// XMAS read left to right along the rows and down the main diagonal of every 4x4 window.
+/// Turning the grid four times covers the other six directions.
+fn count_xmas_facing_one_way(letters: &Grid<char>) -> usize {
+    let in_rows: usize = letters
+        .rows()
+        .map(|row| row.iter().collect::<String>().matches(XMAS).count())
+        .sum();
+    let on_diagonals = letters
+        .windows(XMAS.len(), XMAS.len())
+        .filter(|window| XMAS.chars().enumerate().all(|(i, letter)| window[Coord::from_index(i, i)] == letter))
+        .count();
+    in_rows + on_diagonals
 }
 
-impl Grid {
-    pub fn build_from_file(filename: &str) -> Grid {
-        let lines = read_input_as_lines(filename);
-        let cells: Vec<Vec<Node>> = lines
+fn build_crosses(grid: &Grid<Node>, origin: Coord) -> usize {
+    let top_left_position = Direction::NW.jump_cell(origin);
+    let top_right_position = Direction::NE.jump_cell(origin);
+    let bottom_left_position = Direction::SW.jump_cell(origin);
+    let bottom_right_position = Direction::SE.jump_cell(origin);
+
+    let maybe_top_left = grid.get(top_left_position);
+    let maybe_top_right = grid.get(top_right_position);
+    let maybe_bottom_left = grid.get(bottom_left_position);
+    let maybe_bottom_right = grid.get(bottom_right_position);
+    let maybe_origin = grid.get(origin);
+
+    if
+        let (
+            Some(top_left),
+            Some(top_right),
+            Some(bottom_left),
+            Some(bottom_right),
+            Some(origin_node),
+        ) = (
+            maybe_top_left,
+            maybe_top_right,
+            maybe_bottom_left,
+            maybe_bottom_right,
+            maybe_origin,
+        )
+    {
+        let forward_diag: String = [
+            top_left.symbol,
+            origin_node.symbol,
+            bottom_right.symbol
+        ]
             .into_iter()
-            .map(|row: String| {
-                // Each row becomes its own vector of usize, for following calculations
-                row.chars()
-                    .map(|letter: char| Node { letter: letter, visited: false })
-                    .collect()
-            })
-            .filter(|report: &Vec<Node>| report.len() > 0)
             .collect();
+        let back_diag: String = [bottom_left.symbol, origin_node.symbol, top_right.symbol]
+            .into_iter()
+            .collect();
+        let forward_hit =
+            forward_diag == "MAS" || forward_diag == "SAM";
+        let back_hit = back_diag == "MAS" || back_diag == "SAM";
 
-        let grid_height: i32 = (cells.len() as i32) - 1;
-        let grid_width: i32 = (cells.first().unwrap().len() as i32) - 1;
-
-        Grid {
-            height: grid_height,
-            width: grid_width,
-            cells: cells,
+        match (back_hit, forward_hit) {
+            (true, true) => 1,
+            _ => 0,
         }
+    } else {
+        0
     }
+}
 
-    fn get_cell(&self, position: (i32, i32)) -> Option<&Node> {
-        let (x, y) = position;
-        let x = self.cells
-            .get(y as usize)
-            .map(|row| row.get(x as usize))
-            .flatten();
-
-        x
-    }
+#[derive(Default)]
+pub struct Day4 {
+    grid: Grid<Node>,
 }
-pub fn day4() {
-    let grid = Grid::build_from_file("day4_input.txt");
 
-    // The Crawl: Append <the crawl> to the end of the string  (4 chars), return a list of crawls
-    fn build_fourpairs(
-        grid: Grid,
-        current_node: (i32, i32),
-        current_word: String,
-        direction: &Direction
-    ) -> Vec<String> {
-        let (x, y) = current_node;
-        let current_cell = grid.get_cell(current_node);
-        current_cell
-            .map(|node| {
-                match node.visited {
-                    true => None,
-                    false => {
-                        let mut inner_grid = grid.clone();
-                        inner_grid.cells[y as usize][x as usize] = Node {
-                            letter: node.letter,
-                            visited: true,
-                        };
-                        let mut new_word = current_word.to_owned();
-                        new_word.push(node.letter);
-                        if XMAS.starts_with(&new_word) {
-                            let mut results: Vec<String> = Vec::new();
-                            match new_word.len() {
-                                4 => results.append(Vec::from([new_word]).as_mut()),
-                                _ => {
-                                    let (x_offset, y_offset) = direction.coord_shift();
-                                    let new_y = y + y_offset;
-                                    let new_x = x + x_offset;
-                                    results.append(
-                                        build_fourpairs(
-                                            inner_grid.clone(),
-                                            (new_x, new_y),
-                                            new_word.clone(),
-                                            direction
-                                        ).as_mut()
-                                    );
-                                }
-                            }
-                            Some(results)
-                        } else {
-                            None
-                        }
-                    }
-                }
-            })
-            .flatten()
-            .unwrap_or(Vec::new())
+impl Solution for Day4 {
+    fn parse(&mut self, input: &str) -> AocResult<()> {
+        self.grid = Grid::build_from_input(input)?;
+        Ok(())
     }
 
-    let mut all_words: Vec<String> = Vec::new();
-    for y in 0..=grid.height {
-        for x in 0..=grid.width {
-            Direction::iterator().for_each(|direction| {
-                let grid_iteration = grid.clone();
-                all_words.append(
-                    build_fourpairs(grid_iteration, (x, y), String::new(), direction).as_mut()
-                );
-            });
-        }
+    fn examples(&self) -> &'static [&'static str] {
+        &[include_str!("../input/day4_example1.txt")]
     }
 
-    let count_xmas = all_words
-        .into_iter()
-        .filter(|word| *word == String::from("XMAS"))
-        .count();
-    println!("Part 1 (count all xmas): {}", count_xmas);
-    assert_eq!(count_xmas, 2562);
-}
-
-pub fn day4_part2() {
-    let grid = Grid::build_from_file("day4_input.txt");
-
-    fn build_crosses(grid: &Grid, origin: (i32, i32)) -> usize {
-        let (origin_x, origin_y) = origin;
-        let top_left_position = Direction::NW.jump_cell(origin);
-        let top_right_position = Direction::NE.jump_cell(origin);
-        let bottom_left_position = Direction::SW.jump_cell(origin);
-        let bottom_right_position = Direction::SE.jump_cell(origin);
-
-        let maybe_top_left = grid.get_cell(top_left_position);
-        let maybe_top_right = grid.get_cell(top_right_position);
-        let maybe_bottom_left = grid.get_cell(bottom_left_position);
-        let maybe_bottom_right = grid.get_cell(bottom_right_position);
-        let maybe_origin = grid.get_cell(origin);
-
-        if
-            let (
-                Some(top_left),
-                Some(top_right),
-                Some(bottom_left),
-                Some(bottom_right),
-                Some(origin_node),
-            ) = (
-                maybe_top_left,
-                maybe_top_right,
-                maybe_bottom_left,
-                maybe_bottom_right,
-                maybe_origin,
-            )
-        {
-            let forward_diag: String = vec![
-                top_left.letter,
-                origin_node.letter,
-                bottom_right.letter
-            ]
-                .into_iter()
-                .collect();
-            let back_diag: String = vec![bottom_left.letter, origin_node.letter, top_right.letter]
-                .into_iter()
-                .collect();
-            let forward_hit =
-                forward_diag == String::from("MAS") || forward_diag == String::from("SAM");
-            let back_hit = back_diag == String::from("MAS") || back_diag == String::from("SAM");
-
-            match (back_hit, forward_hit) {
-                (true, true) => 1,
-                _ => 0,
-            }
-        } else {
-            0
+    fn part1(&self) -> AocResult<Answer> {
+        let mut letters = self.grid.map(|node| node.symbol);
+        let mut count_xmas = 0;
+        for _ in 0..4 {
+            count_xmas += count_xmas_facing_one_way(&letters);
+            letters = letters.rotate_cw();
         }
+        Ok(count_xmas.into())
     }
 
-    let mut count: usize = 0;
-    for y in 0..=grid.height {
-        for x in 0..=grid.width {
-            let position = (x, y);
-            let maybe_cell = grid.get_cell(position);
-            if let Some(Node { letter: 'A', visited: _ }) = maybe_cell {
+    fn part2(&self) -> AocResult<Answer> {
+        let mut count: usize = 0;
+        for (position, node) in self.grid.iter() {
+            if let Node { symbol: 'A' } = node {
                 // Only look for crosses if the origin node is an M
-                count += build_crosses(&grid, position);
+                count += build_crosses(&self.grid, position);
             }
         }
+        Ok(count.into())
     }
-    println!("Day 4 Part 2 (X-MAS): {}", count);
-    assert_eq!(count, 1902)
 }
 
-pub fn day5() {
-    #[derive(Clone, Copy, Debug)]
-    struct PageRule {
-        first: usize,
-        second: usize,
-    }
+/// A rule "first|second", filed under `first` in `rules`.
+#[derive(Clone, Copy, Debug)]
+struct PageRule {
+    second: usize,
+}
 
-    let mut rules: HashMap<usize, Vec<PageRule>> = HashMap::new();
+fn row_is_correct(rules: &HashMap<usize, Vec<PageRule>>, pages: &[usize]) -> bool {
+    // curried just so we can get the hashmap without having to pass it around :shrug:
+    pages
+        .iter()
+        .enumerate()
+        .fold(true, |is_correct, (index, page)| {
+            let page_rules = rules.get(page);
+            page_rules
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .all(|rule| {
+                            // Todo:: Find index of all 'second' rule nums; ensure they are > than max of left index
+                            // fold to true if all ok; abort quick on f1alse.
+                            let must_follow_indexes = pages
+                                .iter()
+                                .rposition(|item| item == &rule.second)
+                                .map(|found_index| { found_index > index });
+                            match must_follow_indexes {
+                                Some(true) => true,
+                                Some(false) => false,
+                                None => true,
+                            }
+                        })
+                })
+                .unwrap_or(true) && is_correct
+        })
+}
 
-    let lines = read_input_as_lines("day5_input.txt");
-    // Line that input switches over
-    let input_pivot = 1177;
-    // let input_pivot = 22;
-    let (rules_input, printer_input) = lines.split_at(input_pivot);
+#[derive(Default)]
+pub struct Day5 {
+    rules: HashMap<usize, Vec<PageRule>>,
+    converted_rows: Vec<Vec<usize>>,
+}
 
-    rules_input
-        .into_iter()
-        .filter(|line| !line.is_empty())
-        .for_each(|line| {
-            let (first_raw, second_raw) = line.split_once("|").unwrap();
-            let first = first_raw.parse::<usize>().unwrap();
-            let second = second_raw.parse::<usize>().unwrap();
-            match rules.get_mut(&first) {
-                Some(ruleset) => ruleset.push(PageRule { first, second }),
+impl Solution for Day5 {
+    fn parse(&mut self, input: &str) -> AocResult<()> {
+        let lines: Vec<(usize, &str)> = input.lines().enumerate().collect();
+        // Line that input switches over: the rules end at the first blank line
+        let input_pivot = lines.iter().position(|(_, line)| line.is_empty()).unwrap_or(lines.len());
+        let (rules_input, printer_input) = lines.split_at(input_pivot);
+
+        for &(line_index, line) in rules_input.iter().filter(|(_, line)| !line.is_empty()) {
+            let (first_raw, second_raw) = line
+                .split_once("|")
+                .ok_or_else(|| AocError::at_line(line_index, line, "expected a rule like 47|53"))?;
+            let first = error::parse_number::<usize>(line_index, line, first_raw)?;
+            let second = error::parse_number::<usize>(line_index, line, second_raw)?;
+            match self.rules.get_mut(&first) {
+                Some(ruleset) => ruleset.push(PageRule { second }),
                 _ => {
-                    rules.insert(first, vec![PageRule { first, second }]);
+                    self.rules.insert(first, vec![PageRule { second }]);
                 }
             };
-        });
+        }
 
-    fn row_is_correct(rules: &HashMap<usize, Vec<PageRule>>, pages: &Vec<usize>) -> bool {
-        // curried just so we can get the hashmap without having to pass it around :shrug:
-        pages
+        self.converted_rows = printer_input
             .iter()
-            .enumerate()
-            .fold(true, |is_correct, (index, page)| {
-                let page_rules = rules.get(&page);
-                page_rules
-                    .map(|rules| {
-                        rules
-                            .into_iter()
-                            .map(|rule| {
-                                // Todo:: Find index of all 'second' rule nums; ensure they are > than max of left index
-                                // fold to true if all ok; abort quick on f1alse.
-                                let must_follow_indexes = pages
-                                    .iter()
-                                    .rposition(|item| item == &rule.second)
-                                    .map(|found_index| { found_index > index });
-                                match must_follow_indexes {
-                                    Some(true) => true,
-                                    Some(false) => false,
-                                    None => true,
-                                }
-                            })
-                            .all(|x| x)
-                    })
-                    .unwrap_or(true) && is_correct
+            .filter(|(_, line)| !line.is_empty())
+            .map(|&(line_index, line)| {
+                line
+                    .split(",")
+                    .map(|raw| error::parse_number::<usize>(line_index, line, raw))
+                    .collect::<AocResult<Vec<usize>>>()
             })
+            .collect::<AocResult<_>>()?;
+        Ok(())
     }
 
-    let converted_rows: Vec<Vec<usize>> = printer_input
-        .into_iter()
-        .map(|line| {
-            let pages: Vec<usize> = line
-                .split(",")
-                .map(|raw| raw.parse::<usize>().unwrap())
-                .collect();
-            pages
-        })
-        .collect();
+    fn examples(&self) -> &'static [&'static str] {
+        &[include_str!("../input/day5_example1.txt")]
+    }
 
-    // This could be done in one swoop with the unstable feature drain_filter
-    let correct_rows: Vec<Vec<usize>> = converted_rows
-        .clone()
-        .into_iter()
-        .filter(|row| row_is_correct(&rules, row))
-        .collect();
-    let incorrect_rows: Vec<Vec<usize>> = converted_rows
-        .clone()
-        .into_iter()
-        .filter(|row| !row_is_correct(&rules, row))
-        .collect();
-    let fixed_rows = incorrect_rows.iter().map(|row| {
-        let mut fixed = row.clone();
-        fixed.sort_by(|left, right| {
-            let left_page_rules = rules.get(&left);
-            let right_page_rules = rules.get(&right);
-            if let Some(rules) = left_page_rules {
-                let relevant_left_rule = rules
-                    .iter()
-                    .filter(|rule| rule.second == *right)
-                    .next();
-                if relevant_left_rule.is_some() {
-                    Ordering::Less
-                } else {
-                    Ordering::Greater
-                }
-            } else if let Some(rules) = right_page_rules {
-                let relevant_right_rule = rules
-                    .iter()
-                    .filter(|rule| rule.second == *left)
-                    .next();
-                if relevant_right_rule.is_some() {
-                    Ordering::Greater
+    fn part1(&self) -> AocResult<Answer> {
+        // This could be done in one swoop with the unstable feature drain_filter
+        let correct_mids = self.converted_rows
+            .iter()
+            .filter(|row| row_is_correct(&self.rules, row))
+            .filter_map(|row| { row.get(row.len() / 2).cloned() });
+        let sum_mids: usize = correct_mids.sum();
+        Ok(sum_mids.into())
+    }
+
+    fn part2(&self) -> AocResult<Answer> {
+        let incorrect_rows: Vec<Vec<usize>> = self.converted_rows
+            .clone()
+            .into_iter()
+            .filter(|row| !row_is_correct(&self.rules, row))
+            .collect();
+        let fixed_rows = incorrect_rows.iter().map(|row| {
+            let mut fixed = row.clone();
+            fixed.sort_by(|left, right| {
+                let left_page_rules = self.rules.get(left);
+                let right_page_rules = self.rules.get(right);
+                if let Some(rules) = left_page_rules {
+                    let relevant_left_rule = rules
+                        .iter().find(|rule| rule.second == *right);
+                    if relevant_left_rule.is_some() {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    }
+                } else if let Some(rules) = right_page_rules {
+                    let relevant_right_rule = rules
+                        .iter().find(|rule| rule.second == *left);
+                    if relevant_right_rule.is_some() {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Less
+                    }
                 } else {
                     Ordering::Less
                 }
-            } else {
-                Ordering::Less
-            }
+            });
+            fixed
         });
-        fixed
-    });
-
-    let correct_mids = correct_rows
-        .into_iter()
-        .filter_map(|row| { row.get(row.len() / 2).cloned() });
 
-    let fixed_incorrect_mids = fixed_rows
-        .into_iter()
-        .filter_map(|row| { row.get(row.len() / 2).cloned() });
+        // Get the incorrect rows, then fix and count them.
+        // It would be cleaner & more efficient to fix them in the first iteration (as well as collect correct & incorrect separately)
+        // which I'm only not doing because it's annoying and I don't care about performance
+        let fixed_incorrect_mids = fixed_rows
+            .into_iter()
+            .filter_map(|row| { row.get(row.len() / 2).cloned() });
 
-    // Get the incorrect rows, then fix and count them.
-    // It would be cleaner & more efficient to fix them in the first iteration (as well as collect correct & incorrect separately)
-    // which I'm only not doing because it's annoying and I don't care about performance
+        let sum_incorrect_mids: usize = fixed_incorrect_mids.sum();
+        Ok(sum_incorrect_mids.into())
+    }
+}
 
-    let just_mids: Vec<usize> = correct_mids.clone().collect();
-    println!("correct mids: {:?}", just_mids);
-    let sum_mids: usize = correct_mids.sum();
-    // let sum_incorrect_mids: usize = other_fixed.sum();
-    let sum_incorrect_mids: usize = fixed_incorrect_mids.sum();
-    println!("Part 1 (Sum of Correct Mids): {}", sum_mids);
-    println!("Part 2 (Sum of Fixed Incorrect Mids): {}", sum_incorrect_mids);
+fn guard_loops_at(position: Coord) -> AocError {
+    AocError::new(format!("the guard walks in a loop through ({}, {}) and never leaves the map", position.x, position.y))
 }
 
-pub fn day6() {
-    let mut map = Grid::build_from_file("day6_input.txt");
-    let start_position = map.cells
-        .iter()
-        .enumerate()
-        .fold(None, |guard_position, (guard_y_position, row)| {
-            let guard_x_position = row.iter().position(|x| x.letter == '^');
-            match (guard_position, guard_x_position) {
-                (None, Some(x)) => Some((x as i32, guard_y_position as i32)),
-                (Some(pos), _) => Some(pos),
-                _ => None,
-            }
-        })
-        .unwrap();
-    println!("Start position: {:?}", start_position);
-    println!("Starting Cell: {:?}", map.get_cell(start_position));
-    let (start_x, start_y) = start_position;
-    // Consider the starting node as already visited
-    map.cells[start_y as usize][start_x as usize] = Node { letter: '^', visited: true };
-    let p2_map = map.clone();
-
-    fn traverse_map(map: &mut Grid, direction: Direction, position: (i32, i32)) {
+/// Walks the guard until it leaves the map, recording each step in the "path" layer.
+fn traverse_map(map: &Grid<Node>, path: &mut Layers, mut direction: Direction, mut position: Coord) -> AocResult<()> {
+    // Turning all the way round without a step means the guard is boxed in
+    let mut turns_in_place = 0;
+    loop {
         let next_position = direction.jump_cell(position);
-        let (next_node_x, next_node_y) = next_position;
-        let next_node = map.get_cell(next_position);
-        // println!("Going from {:?} to {:?} with direction {:?}", position, next_node, direction);
-        match next_node {
-            Some(Node { letter: '#', visited: _ }) => {
-                traverse_map(map, direction.rotate_90().unwrap(), position);
+        match map.get(next_position) {
+            Some(Node { symbol: '#' }) => {
+                turns_in_place += 1;
+                if turns_in_place == 4 {
+                    return Err(guard_loops_at(position));
+                }
+                direction = direction.turn_right();
             }
-            Some(node) => {
-                map.cells[next_node_y as usize][next_node_x as usize] = Node {
-                    letter: 'X',
-                    visited: true,
-                };
-                traverse_map(map, direction, next_position);
+            Some(_node) => {
+                // Already been here facing the same way, so it'll go round again forever
+                if !path.add_direction("path", next_position, direction) {
+                    return Err(guard_loops_at(next_position));
+                }
+                turns_in_place = 0;
+                position = next_position;
             }
             None => {
-                // End of the recursion -- we've left the map!
-                ()
+                // We've left the map!
+                return Ok(());
             }
         }
     }
-    traverse_map(&mut map, Direction::N, start_position);
+}
 
-    fn traverse_will_loop(
-        map: &Grid,
-        direction: Direction,
-        position: (i32, i32),
-        previous_turns: &mut Vec<((i32, i32), Direction)>
-    ) -> bool {
+fn traverse_will_loop(map: &Grid<Node>, mut direction: Direction, mut position: Coord) -> bool {
+    let mut previous_turns: Vec<(Coord, Direction)> = Vec::new();
+    loop {
         let next_position = direction.jump_cell(position);
-        let next_node = map.get_cell(next_position);
-        let rotated = direction.rotate_90().unwrap();
-        match next_node {
-            Some(Node { letter: '#', visited: _ }) => {
-                let already_encountered = previous_turns
-                    .iter()
-                    .filter(|(prev_position, prev_direction)| {
-                        // println!("{:?} == {:?} && {:?} == {:?}", prev_position, position, prev_direction, direction);
-                        *prev_position == position && *prev_direction == direction
-                    })
-                    .next();
-                match already_encountered {
-                    Some(_) => true,
-                    _ => {
-                        previous_turns.push((position, direction.clone()));
-                        traverse_will_loop(
-                            map,
-                            direction.rotate_90().unwrap(),
-                            position,
-                            previous_turns
-                        )
-                    }
+        match map.get(next_position) {
+            Some(Node { symbol: '#' }) => {
+                if previous_turns.contains(&(position, direction)) {
+                    return true;
                 }
+                previous_turns.push((position, direction));
+                direction = direction.turn_right();
             }
-            Some(_) => { traverse_will_loop(map, direction, next_position, previous_turns) }
+            Some(_) => position = next_position,
             None => {
-                // End of the recursion -- we've left the map!
-                // println!("Traversed paths: {:?}", traversed_paths);
-                false
+                // We've left the map!
+                return false;
             }
         }
     }
+}
 
-    fn traverse_map_for_loops(
-        map: &Grid,
-        direction: Direction,
-        position: (i32, i32)
-    ) -> Vec<(i32, i32)> {
-        // TODO:: This currently comes up with 6004 new blockers, which is considered "too high". There's a bug, somewhere.
+fn traverse_map_for_loops(map: &Grid<Node>, mut direction: Direction, mut position: Coord) -> AocResult<Vec<Coord>> {
+    // TODO:: This currently comes up with 6004 new blockers, which is considered "too high". There's a bug, somewhere.
+    let mut looping_blockers = Vec::new();
+    let mut path: Layers = Layers::for_grid(map);
+    path.add_direction("path", position, direction);
+    let mut turns_in_place = 0;
+    loop {
         let next_position = direction.jump_cell(position);
-        let (next_node_x, next_node_y) = next_position;
-        let next_node = map.get_cell(next_position);
-        // println!("Going from {:?} to {:?} with direction {:?}", position, next_node, direction);
-        match next_node {
-            Some(Node { letter: '#', visited: _ }) => {
+        match map.get(next_position) {
+            Some(Node { symbol: '#' }) => {
                 // If it's already a blocker, we don't have to run a loop test.
-                traverse_map_for_loops(map, direction.rotate_90().unwrap(), position)
+                turns_in_place += 1;
+                if turns_in_place == 4 {
+                    return Err(guard_loops_at(position));
+                }
+                direction = direction.turn_right();
             }
             Some(_node) => {
                 // This is where we could put a new obstruction to force us to go in circles
-                let alternative_route = direction.clone().rotate_90().unwrap();
-                
                 let mut blocked_map = map.clone();
-                blocked_map.cells[next_node_y as usize][next_node_x as usize] = {
-                    Node { visited: false, letter: '#' }
-                };
-                let will_loop_if_blocked = traverse_will_loop(
-                    &blocked_map,
-                    alternative_route,
-                    position,
-                    &mut Vec::new()
-                );
-                let extra_loops = traverse_map_for_loops(map, direction, next_position);
-                if will_loop_if_blocked {
-                    vec![next_position].into_iter().chain(extra_loops.into_iter()).collect()
-                } else {
-                    extra_loops
+                blocked_map[next_position] = Node { symbol: '#' };
+                if traverse_will_loop(&blocked_map, direction.turn_right(), position) {
+                    looping_blockers.push(next_position);
+                }
+                if !path.add_direction("path", next_position, direction) {
+                    return Err(guard_loops_at(next_position));
                 }
+                turns_in_place = 0;
+                position = next_position;
             }
-            None => { Vec::new() }
+            None => return Ok(looping_blockers),
         }
     }
+}
 
-    let mut looping_new_blockers = traverse_map_for_loops(&p2_map, Direction::N, start_position);
+#[derive(Default)]
+pub struct Day6 {
+    map: Grid<Node>,
+    start_position: Coord,
+}
 
-    let sum_visited_cells = map.cells.into_iter().fold(0, |total, row| {
-        total +
-            row
-                .iter()
-                .filter(|n| n.visited)
-                .count()
-    });
+impl Solution for Day6 {
+    fn parse(&mut self, input: &str) -> AocResult<()> {
+        self.map = Grid::build_from_input(input)?;
+        self.start_position = self.map
+            .iter()
+            .find(|(_, node)| node.symbol == '^')
+            .map(|(position, _)| position)
+            .ok_or_else(|| AocError::new("no guard (^) on the map"))?;
+        Ok(())
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[include_str!("../input/day6_example1.txt")]
+    }
+
+    fn part1(&self) -> AocResult<Answer> {
+        let mut path: Layers = Layers::for_grid(&self.map);
+        // Consider the starting node as already visited
+        path.add_direction("path", self.start_position, Direction::N);
+        traverse_map(&self.map, &mut path, Direction::N, self.start_position)?;
 
-    looping_new_blockers.sort();
-    println!("looping blockers {:?}", looping_new_blockers);
-    looping_new_blockers.dedup();
-    // TODO:: Part 2 answer is still wrong. giving up after hours of bashing my head against the wall.
-    println!("Part 1 (sum of cells visited): {}", sum_visited_cells);
-    println!("Part 2 (number of potential infinite loop blockers: {})", looping_new_blockers.len());
+        let sum_visited_cells = path.count("path");
+        Ok(sum_visited_cells.into())
+    }
+
+    fn part2(&self) -> AocResult<Answer> {
+        let mut looping_new_blockers = traverse_map_for_loops(&self.map, Direction::N, self.start_position)?;
+        looping_new_blockers.sort();
+        looping_new_blockers.dedup();
+        // TODO:: Part 2 answer is still wrong. giving up after hours of bashing my head against the wall.
+        // Known-bad guesses live in input/day6_input.answers.
+        Ok(looping_new_blockers.len().into())
+    }
 }
 
-pub fn day7() {
-    #[derive(Clone, Debug)]
-    struct MaybeAnswerable {
-        answer: i64,
-        ordered_operatees: Vec<i64>
-    }
-    enum Operators {
-        Add,
-        Multiply
-    }
-    let maybe_answerables: Vec<MaybeAnswerable> = read_input_as_lines("day7_input.txt").into_iter().filter_map(|row| {
-        let split_row = row.split_once(": ");
-        if let Some((answer, operatees)) = split_row {
-            Some(MaybeAnswerable {
-                answer: answer.parse().unwrap(),
-                ordered_operatees: operatees.split_whitespace().map(|operatee| operatee.parse::<i64>().unwrap()).collect()
-            })
-        } else {
-            None
+#[derive(Clone, Debug)]
+struct MaybeAnswerable {
+    answer: i64,
+    ordered_operatees: Vec<i64>
+}
+
+// Recursive fold right to evaluate: The current index Plus or Times the calculated total of all indexes to its left, for each + and * multiple possible.
+// Anything that doesn't fit in an i64 can't be the answer, so it's dropped rather than wrapped.
+fn visually_combine(left: i64, right: i64) -> Option<i64> {
+    let mut string_repr = left.to_string();
+    string_repr.push_str(&right.to_string());
+    string_repr.parse().ok()
+}
+
+fn calculate(ordered_operatees: Vec<i64>, with_concatenation: bool) -> Vec<i64>  {
+    let mut operatee_iterator = ordered_operatees.into_iter();
+    // There should be no empty operatee lists for this initial state.
+    let start = operatee_iterator.next();
+    match start {
+        Some(operatee) => {
+            let remaining: Vec<i64> = operatee_iterator.collect();
+            if remaining.is_empty() {
+                return vec![operatee];
+            }
+            let downstream: Vec<i64> = calculate(remaining, with_concatenation);
+            // Part 1 only has + and *; the concatenation operator was added in part 2.
+            let concatenated: Vec<i64> = match with_concatenation {
+                true => downstream.iter().filter_map(|item| visually_combine(*item, operatee)).collect(),
+                false => Vec::new(),
+            };
+            downstream
+                .iter()
+                .filter_map(|item| operatee.checked_add(*item))
+                .chain(concatenated)
+                .chain(downstream.iter().filter_map(|item| operatee.checked_mul(*item)))
+                .collect()
+        },
+        _ => {
+            Vec::new()
         }
-    }).collect();
-
-    // Recursive fold right to evaluate: The current index Plus or Times the calculated total of all indexes to its left, for each + and * multiple possible
-    fn visually_combine(left: i64, right: i64) -> i64 {
-        let mut string_repr = left.to_string();
-        string_repr.push_str(&right.to_string());
-        string_repr.parse().unwrap()
-    }
-
-    fn calculate(ordered_operatees: Vec<i64>, prev: i64) -> Vec<i64>  {
-        let mut operatee_iterator = ordered_operatees.into_iter();
-        // There should be no empty operatee lists for this initial state.
-        let start = operatee_iterator.next();
-        match start {
-            Some(operatee) => {
-                let downstream: Vec<i64> = calculate(operatee_iterator.collect(), operatee);
-                // !("{:?} */+ downstream: {:?}", operatee, downstream);
-                if !downstream.is_empty() {
-                    downstream
-                        .clone()
-                        .iter()
-                        .map(|item| operatee + item)
-                        .chain(downstream.clone().iter()
-                            .map(|item| visually_combine(*item, operatee))
-                        )
-                        .chain(downstream.into_iter()
-                            .map(|item| operatee * item)
-                        )
-                        .collect()
-                } else {
-                    vec![operatee]
-                }
-            },
-            _ => {
-                Vec::new()
+    }
+}
+
+#[derive(Default)]
+pub struct Day7 {
+    maybe_answerables: Vec<MaybeAnswerable>,
+}
+
+impl Day7 {
+    fn answerable_total(&self, with_concatenation: bool) -> AocResult<i64> {
+        let mut correct_answers = self.maybe_answerables.iter().filter(|maybe_answerable| {
+            let answer_fold = maybe_answerable.ordered_operatees.clone().into_iter().rev().collect();
+            let potential_answers = calculate(answer_fold, with_concatenation);
+            potential_answers.into_iter().any(|answer| answer == maybe_answerable.answer)
+        });
+
+        correct_answers.try_fold(0i64, |total, maybe_answerable| {
+            total
+                .checked_add(maybe_answerable.answer)
+                .ok_or_else(|| AocError::new("the total of the true equations doesn't fit in a 64 bit number"))
+        })
+    }
+}
+
+impl Solution for Day7 {
+    fn parse(&mut self, input: &str) -> AocResult<()> {
+        self.maybe_answerables = input.split("\n").enumerate().filter(|(_, row)| !row.is_empty()).map(|(row_index, row)| {
+            let split_row = row.split_once(": ");
+            if let Some((answer, operatees)) = split_row {
+                Ok(MaybeAnswerable {
+                    answer: error::parse_number(row_index, row, answer)?,
+                    ordered_operatees: operatees
+                        .split_whitespace()
+                        .map(|operatee| error::parse_number::<i64>(row_index, row, operatee))
+                        .collect::<AocResult<_>>()?
+                })
+            } else {
+                Err(AocError::at_line(row_index, row, "expected a line like 190: 10 19"))
             }
+        }).collect::<AocResult<_>>()?;
+        Ok(())
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[include_str!("../input/day7_example1.txt")]
+    }
+
+    fn part1(&self) -> AocResult<Answer> {
+        Ok(self.answerable_total(false)?.into())
+    }
+
+    fn part2(&self) -> AocResult<Answer> {
+        Ok(self.answerable_total(true)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Part;
+
+    fn solve(day: &mut impl Solution, input: &str, part: Part) -> AocResult<Answer> {
+        day.parse(input)?;
+        day.solve(part)
+    }
+
+    #[test]
+    fn boxed_in_guard_is_an_error() {
+        let error = solve(&mut Day6::default(), ".#.\n#^#\n.#.\n", Part::One).unwrap_err();
+        assert!(error.to_string().contains("loop"), "{}", error);
+    }
+
+    #[test]
+    fn looping_guard_is_an_error() {
+        let map = ".#...\n....#\n.^...\n#....\n...#.\n";
+        assert!(solve(&mut Day6::default(), map, Part::One).is_err());
+        assert!(solve(&mut Day6::default(), map, Part::Two).is_err());
+    }
+
+    #[test]
+    fn overflowing_operators_are_dropped() {
+        // 1000000000 || 1000000000 doesn't fit in an i64, but 1000000000 * 1000000000 does and is the answer
+        let equation = "1000000000000000000: 1000000000 1000000000\n";
+        for part in Part::BOTH {
+            assert_eq!(solve(&mut Day7::default(), equation, part), Ok(Answer::Num(1_000_000_000_000_000_000)));
         }
     }
-    let correct_answers = maybe_answerables.iter().filter(|maybe_answerable| {
-        // println!("Processing {:?}", maybe_answerable);
-        let answer_fold = maybe_answerable.ordered_operatees.clone().into_iter().rev().collect();
-        let potential_answers = calculate(answer_fold, 0);
-        potential_answers.into_iter().any(|answer| answer == maybe_answerable.answer)
-    });
 
-    let answerable_total = correct_answers.fold(0, |total, maybe_answerable| total + maybe_answerable.answer);
-    println!("Part 1 (Total from answerable rows): {}", answerable_total);
-}
\ No newline at end of file
+    #[test]
+    fn overflowing_total_is_an_error() {
+        let equations = "9000000000000000000: 9000000000000000000\n9000000000000000000: 9000000000000000000\n";
+        let error = solve(&mut Day7::default(), equations, Part::One).unwrap_err();
+        assert!(error.to_string().contains("64 bit"), "{}", error);
+    }
+}